@@ -7,7 +7,6 @@ use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 fn get_robots() -> impl Iterator<Item = (String, String)> {
     fs::read_dir("./test-data")
         .unwrap()
-        .into_iter()
         .filter_map(|item| item.ok())
         .filter_map(|entry| {
             let name = String::from(entry.file_name().to_string_lossy());
@@ -0,0 +1,548 @@
+use std::{borrow::Cow, ops::AddAssign, time::Duration};
+
+#[cfg(feature = "async")]
+use async_trait::async_trait;
+#[cfg(feature = "async")]
+use futures_lite::stream::{Stream, StreamExt};
+
+#[cfg(feature = "async")]
+use crate::AddAssignAsync;
+use crate::{
+    CleanParam, ContentSignals, ParseProfile, Permission, PrefixTrie, Recogniser, RequestRate,
+    SingleBot, UserAgent, Verdict,
+    cleanparam::clean_path,
+    singlebot::{is_global, merge_content_signals, parse_crawl_delay},
+};
+
+/// Marks rules which apply regardless of the user agent, like the permission of paths no rule covers.
+const EVERY_AGENT: u32 = u32::MAX;
+
+/// The directives following `User-agent` lines.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Segment {
+    /// The values of the `User-agent` lines, in lowercase.
+    /// Consecutive lines only share a segment if the profile merges them.
+    agents: Vec<String>,
+    /// The number of the `robots.txt` the segment was added with.
+    /// Named user agents only take precedence over `*` within the same `robots.txt`.
+    source: u32,
+    crawl_delay: Option<Duration>,
+    request_rate: Option<RequestRate>,
+    content_signals: Option<ContentSignals>,
+}
+
+impl AddAssign<String> for AgentBot {
+    fn add_assign(&mut self, rhs: String) {
+        self.extend(SingleBot::capture_str(rhs.as_str(), self.recogniser));
+    }
+}
+
+impl AddAssign<&String> for AgentBot {
+    fn add_assign(&mut self, rhs: &String) {
+        self.extend(SingleBot::capture_str(rhs.as_str(), self.recogniser));
+    }
+}
+
+impl AddAssign<&str> for AgentBot {
+    fn add_assign(&mut self, rhs: &str) {
+        self.extend(SingleBot::capture_str(rhs, self.recogniser));
+    }
+}
+
+impl AddAssign<std::fs::File> for AgentBot {
+    fn add_assign(&mut self, rhs: std::fs::File) {
+        self.extend(SingleBot::capture_file(rhs, self.recogniser));
+    }
+}
+
+impl<T> AddAssign<std::io::BufReader<T>> for AgentBot
+where
+    T: std::io::Read,
+{
+    fn add_assign(&mut self, rhs: std::io::BufReader<T>) {
+        self.extend(SingleBot::capture_reader(rhs, self.recogniser));
+    }
+}
+
+impl<T> From<T> for AgentBot
+where
+    AgentBot: AddAssign<T>,
+{
+    fn from(value: T) -> Self {
+        let mut bot = Self::new();
+        bot += value;
+        bot
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<T> AddAssignAsync<futures_lite::io::BufReader<T>> for AgentBot
+where
+    T: futures_lite::AsyncRead + Unpin + Send,
+{
+    async fn add_async(&mut self, rhs: futures_lite::io::BufReader<T>)
+    where
+        T: 'async_trait,
+    {
+        self.extend_async(SingleBot::capture_reader_async(rhs, self.recogniser))
+            .await;
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+#[async_trait]
+impl AddAssignAsync<tokio::fs::File> for AgentBot {
+    async fn add_async(&mut self, rhs: tokio::fs::File) {
+        self.extend_async(SingleBot::capture_file_tokio(rhs, self.recogniser))
+            .await;
+    }
+}
+
+#[cfg(feature = "async-async-std")]
+#[async_trait]
+impl AddAssignAsync<async_std::fs::File> for AgentBot {
+    async fn add_async(&mut self, rhs: async_std::fs::File) {
+        self.extend_async(SingleBot::capture_file_asyncstd(rhs, self.recogniser))
+            .await;
+    }
+}
+
+#[cfg(feature = "async-smol")]
+#[async_trait]
+impl AddAssignAsync<smol::fs::File> for AgentBot {
+    async fn add_async(&mut self, rhs: smol::fs::File) {
+        self.extend_async(SingleBot::capture_file_smol(rhs, self.recogniser))
+            .await;
+    }
+}
+
+impl Default for AgentBot {
+    fn default() -> Self {
+        AgentBot::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Used to check the `robots.txt` of a single Host for any user agent.
+///
+/// Unlike [`SingleBot`], which only keeps the rules for the user agent it was created for, every group is kept
+/// and the groups applying to a user agent are selected when checking. For every user agent, the permissions are
+/// the same as those of a [`SingleBot`] created for the user agent in lowercase.
+pub struct AgentBot {
+    prefixes: PrefixTrie<u32>,
+    /// The rules of every pattern in order of appearance, as segment and permission.
+    rules: Vec<Vec<(u32, Permission)>>,
+    segments: Vec<Segment>,
+    sitemaps: Vec<String>,
+    host: Option<String>,
+    clean_params: Vec<CleanParam>,
+    sources: u32,
+    profile: ParseProfile,
+    recogniser: Recogniser,
+}
+
+/// An [`AgentBot`] bound to a user agent, checking paths like a [`SingleBot`] created for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AgentView<'a> {
+    bot: &'a AgentBot,
+    selected: Cow<'a, [bool]>,
+}
+
+impl AgentBot {
+    /// Creates a new [`AgentBot`].
+    pub fn new() -> Self {
+        AgentBot::with_profile(ParseProfile::default())
+    }
+
+    /// Creates a new [`AgentBot`] which interprets `robots.txt`s like the crawlers of a [`ParseProfile`].
+    pub fn with_profile(profile: ParseProfile) -> Self {
+        let mut prefixes = PrefixTrie::new();
+        prefixes.insert("", 0);
+        AgentBot {
+            prefixes,
+            rules: vec![vec![(EVERY_AGENT, Permission::Unspecified)]],
+            segments: Vec::new(),
+            sitemaps: Vec::new(),
+            host: None,
+            clean_params: Vec::new(),
+            sources: 0,
+            profile,
+            recogniser: profile.recogniser(),
+        }
+    }
+
+    /// Returns the profile the bot interprets `robots.txt`s with.
+    pub fn profile(&self) -> ParseProfile {
+        self.profile
+    }
+
+    /// Sets how the directives of newly added `robots.txt`s are recognised.
+    /// Defaults to [`ParseProfile::recogniser`].
+    pub fn set_recogniser(&mut self, recogniser: Recogniser) {
+        self.recogniser = recogniser;
+    }
+
+    /// Creates a new [`AgentBot`] which denies every path to every user agent.
+    /// This is how a host has to be treated if its `robots.txt` is unreachable.
+    pub fn deny_all() -> Self {
+        let mut bot = AgentBot::new();
+        bot.deny_unspecified();
+        bot
+    }
+
+    /// Denies every path which isn't covered by a rule.
+    pub(crate) fn deny_unspecified(&mut self) {
+        self.rules[0].push((EVERY_AGENT, Permission::Denied));
+    }
+
+    /// Shrinks the internal data structure, saving a few bytes.
+    pub fn shrink(&mut self) {
+        self.prefixes.shrink();
+        self.rules.shrink_to_fit();
+        for rules in &mut self.rules {
+            rules.shrink_to_fit();
+        }
+        self.segments.shrink_to_fit();
+        self.sitemaps.shrink_to_fit();
+        self.clean_params.shrink_to_fit();
+    }
+
+    /// Estimates the number of bytes occupied by the bot, including its heap allocations.
+    pub fn approximate_size(&self) -> usize {
+        size_of::<Self>() - size_of::<PrefixTrie<u32>>()
+            + self.prefixes.approximate_size()
+            + self.rules.capacity() * size_of::<Vec<(u32, Permission)>>()
+            + self
+                .rules
+                .iter()
+                .map(|rules| rules.capacity() * size_of::<(u32, Permission)>())
+                .sum::<usize>()
+            + self.segments.capacity() * size_of::<Segment>()
+            + self
+                .segments
+                .iter()
+                .map(|segment| {
+                    segment.agents.capacity() * size_of::<String>()
+                        + segment.agents.iter().map(String::capacity).sum::<usize>()
+                })
+                .sum::<usize>()
+            + self.sitemaps.capacity() * size_of::<String>()
+            + self.sitemaps.iter().map(String::capacity).sum::<usize>()
+            + self.host.as_ref().map_or(0, String::capacity)
+            + self.clean_params.capacity() * size_of::<CleanParam>()
+            + self
+                .clean_params
+                .iter()
+                .map(CleanParam::heap_size)
+                .sum::<usize>()
+    }
+
+    /// Binds the bot to a user agent. Without user agent, only the `*` groups apply.
+    pub fn for_agent(&self, user_agent: Option<&str>) -> AgentView<'_> {
+        AgentView::new(self, Cow::Owned(self.selection(user_agent)))
+    }
+
+    /// Returns the first candidate of a parsed `User-Agent` header named by a `User-agent` line.
+    /// Returns `None` if only the `*` groups apply.
+    pub fn resolve(&self, user_agent: &UserAgent) -> Option<String> {
+        user_agent.candidates().into_iter().find(|candidate| {
+            self.segments
+                .iter()
+                .any(|segment| self.names(segment, candidate))
+        })
+    }
+
+    /// Binds the bot to the first candidate of a parsed `User-Agent` header named by the `robots.txt`, falling back to `*`.
+    pub fn for_user_agent(&self, user_agent: &UserAgent) -> AgentView<'_> {
+        self.for_agent(self.resolve(user_agent).as_deref())
+    }
+
+    /// Checks the permission defined for a specific URL for a user agent.
+    pub fn check(&self, user_agent: &str, url: &str) -> Permission {
+        self.for_agent(Some(user_agent)).check(url)
+    }
+
+    /// Checks the permission defined for a specific URL for a user agent and tells which rule decided it.
+    pub fn explain(&self, user_agent: &str, url: &str) -> Verdict {
+        self.for_agent(Some(user_agent)).explain(url)
+    }
+
+    /// Returns the user agents named by `User-agent` lines in lowercase, without duplicates and `*`.
+    pub fn user_agents(&self) -> Vec<&str> {
+        let mut agents: Vec<&str> = Vec::new();
+        for agent in self.segments.iter().flat_map(|segment| &segment.agents) {
+            if agent != "*" && !agents.contains(&agent.as_str()) {
+                agents.push(agent);
+            }
+        }
+        agents
+    }
+
+    /// Returns the locations of the sitemaps listed by `Sitemap` directives, regardless of user agent.
+    pub fn sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+
+    /// Returns the preferred mirror of the site, as specified by the first `Host` directive, regardless of user agent.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Returns the `Clean-param` directives, regardless of user agent.
+    pub fn clean_params(&self) -> &[CleanParam] {
+        &self.clean_params
+    }
+
+    /// Removes the query parameters listed by the `Clean-param` directives covering a path, like `/maps?ll=1&z=2` to `/maps?z=2`.
+    pub fn clean_path(&self, url: &str) -> String {
+        clean_path(&self.clean_params, url)
+    }
+
+    /// Tells for every segment whether it applies to a user agent.
+    pub(crate) fn selection(&self, user_agent: Option<&str>) -> Vec<bool> {
+        let user_agent = user_agent.map(str::to_lowercase);
+        let names = |segment: &Segment| {
+            user_agent
+                .as_ref()
+                .is_some_and(|user_agent| self.names(segment, user_agent))
+        };
+        // Like `SingleBot`, a `robots.txt` naming the user agent anywhere ignores its `*` groups.
+        let mut precise = vec![false; self.sources as usize];
+        for segment in self.segments.iter().filter(|segment| names(segment)) {
+            precise[segment.source as usize] = true;
+        }
+        self.segments
+            .iter()
+            .map(|segment| {
+                if precise[segment.source as usize] {
+                    names(segment)
+                } else {
+                    segment.agents.iter().any(|agent| agent == "*")
+                }
+            })
+            .collect()
+    }
+
+    /// Checks whether a segment names a user agent, given in lowercase.
+    fn names(&self, segment: &Segment, user_agent: &str) -> bool {
+        segment
+            .agents
+            .iter()
+            .any(|agent| agent != "*" && self.profile.names(agent, user_agent))
+    }
+
+    fn insert(&mut self, key: &str, segment: u32, permission: Permission) {
+        let idx = self.rules.len() as u32;
+        match self.prefixes.insert(key, idx) {
+            Some(prev) => {
+                self.prefixes.insert(key, prev);
+                self.rules[prev as usize].push((segment, permission));
+            }
+            None => self.rules.push(vec![(segment, permission)]),
+        }
+    }
+
+    fn apply(&mut self, segment: Option<u32>, (op, val): (String, String)) {
+        match op.as_str() {
+            "sitemap" => {
+                if !self.sitemaps.contains(&val) {
+                    self.sitemaps.push(val);
+                }
+                return;
+            }
+            "host" => {
+                if self.host.is_none() && !val.is_empty() {
+                    self.host = Some(val);
+                }
+                return;
+            }
+            "clean-param" => {
+                if let Some(clean_param) = CleanParam::parse(&val)
+                    && !self.clean_params.contains(&clean_param)
+                {
+                    self.clean_params.push(clean_param);
+                }
+                return;
+            }
+            _ => {}
+        }
+        // Rules before the first `User-agent` belong to no group.
+        let Some(segment) = segment else {
+            return;
+        };
+        let state = &mut self.segments[segment as usize];
+        match op.as_str() {
+            "allow" => self.insert(&val, segment, Permission::Allowed),
+            "disallow" => self.insert(&val, segment, Permission::Denied),
+            "crawl-delay" if state.crawl_delay.is_none() => {
+                state.crawl_delay = parse_crawl_delay(&val);
+            }
+            "request-rate" if state.request_rate.is_none() => {
+                state.request_rate = RequestRate::parse(&val);
+            }
+            "content-signal" => merge_content_signals(&mut state.content_signals, &val),
+            "crawl-delay" | "request-rate" => {}
+            _ => unreachable!(),
+        }
+    }
+
+    fn extend(&mut self, captures: impl Iterator<Item = (String, String)>) {
+        let source = self.sources;
+        self.sources += 1;
+        let profile = self.profile;
+        let mut segment = None;
+        let mut after_user_agent = false;
+
+        let captures = captures
+            .filter_map(|pair| profile.prepare(pair))
+            .map(SingleBot::decode);
+        for (op, val) in captures {
+            let is_user_agent = op == "user-agent";
+            if is_user_agent
+                && after_user_agent
+                && profile.merges_user_agents()
+                && let Some(idx) = segment
+            {
+                self.segments[idx as usize].agents.push(val.to_lowercase());
+            } else if is_user_agent {
+                segment = Some(self.push_segment(val.to_lowercase(), source));
+            } else {
+                // Rules before the first `User-agent` form a `*` group, if the profile keeps them.
+                if segment.is_none() && !is_global(&op) && profile.keeps_leading_rules() {
+                    segment = Some(self.push_segment("*".to_owned(), source));
+                }
+                self.apply(segment, (op, val));
+            }
+            after_user_agent = is_user_agent;
+        }
+    }
+
+    fn push_segment(&mut self, agent: String, source: u32) -> u32 {
+        self.segments.push(Segment {
+            agents: vec![agent],
+            source,
+            crawl_delay: None,
+            request_rate: None,
+            content_signals: None,
+        });
+        self.segments.len() as u32 - 1
+    }
+
+    #[cfg(feature = "async")]
+    async fn extend_async(&mut self, captures: impl Stream<Item = (String, String)>) {
+        let captures: Vec<_> = captures.collect().await;
+        self.extend(captures.into_iter());
+    }
+}
+
+impl<'a> AgentView<'a> {
+    pub(crate) fn new(bot: &'a AgentBot, selected: Cow<'a, [bool]>) -> Self {
+        Self { bot, selected }
+    }
+
+    /// Returns the underlying [`AgentBot`].
+    pub fn bot(&self) -> &'a AgentBot {
+        self.bot
+    }
+
+    /// Returns the permission of the last rule for a pattern applying to the user agent.
+    fn decide(&self, idx: u32) -> Option<Permission> {
+        let mut rules = self.bot.rules[idx as usize]
+            .iter()
+            .rev()
+            .filter(|(segment, _)| *segment == EVERY_AGENT || self.selected[*segment as usize])
+            .map(|(_, permission)| *permission);
+        let last = rules.next()?;
+        if self.bot.profile.prefers_allow()
+            && rules.any(|permission| permission == Permission::Allowed)
+        {
+            return Some(Permission::Allowed);
+        }
+        Some(last)
+    }
+
+    /// Checks the permission defined for a specific URL.
+    pub fn check(&self, url: &str) -> Permission {
+        let check = |url: &str| {
+            self.bot
+                .prefixes
+                .get_by(url, |idx| self.decide(idx))
+                .unwrap()
+        };
+        let permission = check(url);
+        match self.bot.profile.equivalent_path(url) {
+            Some(path) if permission == Permission::Unspecified => check(&path),
+            _ => permission,
+        }
+    }
+
+    /// Checks the permission defined for a specific URL and tells which rule decided it.
+    pub fn explain(&self, url: &str) -> Verdict {
+        let explain = |url: &str| {
+            self.bot
+                .prefixes
+                .get_match_by(url, |idx| self.decide(idx))
+                .unwrap()
+        };
+        let (mut permission, mut pattern) = explain(url);
+        if permission == Permission::Unspecified
+            && let Some(path) = self.bot.profile.equivalent_path(url)
+        {
+            (permission, pattern) = explain(&path);
+        }
+        let rule = (!pattern.is_empty()).then(|| pattern.replace('\0', "$"));
+        Verdict { permission, rule }
+    }
+
+    /// Returns the patterns of the rules applying to the user agent along with their permission, in no particular order.
+    /// Patterns are given like in [`Verdict::rule`], and a pattern given several times appears once with its deciding permission.
+    pub fn rules(&self) -> Vec<(String, Permission)> {
+        self.bot
+            .prefixes
+            .entries()
+            .into_iter()
+            .filter(|(pattern, _)| !pattern.is_empty())
+            .filter_map(|(pattern, idx)| {
+                let permission = self.decide(idx)?;
+                Some((pattern.replace('\0', "$"), permission))
+            })
+            .collect()
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &'a Segment> + '_ {
+        self.bot
+            .segments
+            .iter()
+            .zip(self.selected.iter())
+            .filter(|(_, selected)| **selected)
+            .map(|(segment, _)| segment)
+    }
+
+    /// Returns the delay between two requests, as specified by the first `Crawl-delay` directive applying to the user agent.
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.segments().find_map(|segment| segment.crawl_delay)
+    }
+
+    /// Returns the rate at which documents may be requested, as specified by the first `Request-rate` directive applying to the user agent.
+    pub fn request_rate(&self) -> Option<RequestRate> {
+        self.segments().find_map(|segment| segment.request_rate)
+    }
+
+    /// Returns how content may be used, as specified by the `Content-Signal` directives applying to the user agent.
+    /// For every use, the first directive mentioning it applies.
+    pub fn content_signals(&self) -> Option<ContentSignals> {
+        let mut signals: Option<ContentSignals> = None;
+        for segment in self.segments() {
+            if let Some(other) = &segment.content_signals {
+                signals.get_or_insert_default().merge(other);
+            }
+        }
+        signals
+    }
+
+    /// Returns the locations of the sitemaps listed by `Sitemap` directives, regardless of user agent.
+    pub fn sitemaps(&self) -> &'a [String] {
+        &self.bot.sitemaps
+    }
+}
@@ -0,0 +1,104 @@
+use url::Url;
+
+use crate::{CheckError, MetaBot, Permission, SingleBot, SitemapError, SitemapItem, Verdict};
+
+/// Allows for explaining which rule decides the permission for a URL.
+pub trait ExplainURL {
+    /// Checks the permission for a URL and tells which rule decided it.
+    /// # Errors
+    /// Will return `Err` if URL doesn't contain host.
+    fn explain_url(&self, url: &Url) -> Result<Verdict, CheckError>;
+}
+
+impl ExplainURL for SingleBot {
+    fn explain_url(&self, url: &Url) -> Result<Verdict, CheckError> {
+        Ok(self.explain(url.path()))
+    }
+}
+
+impl ExplainURL for MetaBot {
+    fn explain_url(&self, url: &Url) -> Result<Verdict, CheckError> {
+        self.explain(url)
+    }
+}
+
+/// A sitemap item along with the verdict of the `robots.txt` on its location.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AuditedItem {
+    /// The item listed by the sitemap.
+    pub item: SitemapItem,
+    /// The permission for the location of the item and the rule which decided it.
+    pub verdict: Verdict,
+}
+
+/// Sitemap items partitioned by whether the `robots.txt` allows crawling their location.
+///
+/// Denied items are contradictions between a sitemap and the `robots.txt`: they are listed for crawling, yet crawlers may not fetch them.
+#[derive(Clone, Debug, Default)]
+pub struct SitemapAudit {
+    /// Items whose location may be crawled, either because a rule allows it or because no rule matches.
+    pub allowed: Vec<AuditedItem>,
+    /// Items whose location is disallowed.
+    pub denied: Vec<AuditedItem>,
+    /// Items whose location isn't a valid URL with host.
+    pub invalid: Vec<(SitemapItem, CheckError)>,
+}
+
+impl SitemapAudit {
+    /// Creates a new, empty [`SitemapAudit`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Audits all `items` against `bot`.
+    pub fn audit(bot: &impl ExplainURL, items: impl IntoIterator<Item = SitemapItem>) -> Self {
+        let mut audit = Self::new();
+        for item in items {
+            audit.push(bot, item);
+        }
+        audit
+    }
+
+    /// Audits the items read from a sitemap against `bot`, e.g. those of a [`crate::SitemapReader`].
+    /// # Errors
+    /// Will return `Err` if reading the sitemap fails.
+    pub fn try_audit(
+        bot: &impl ExplainURL,
+        items: impl IntoIterator<Item = Result<SitemapItem, SitemapError>>,
+    ) -> Result<Self, SitemapError> {
+        let mut audit = Self::new();
+        for item in items {
+            audit.push(bot, item?);
+        }
+        Ok(audit)
+    }
+
+    /// Audits a single item against `bot`.
+    pub fn push(&mut self, bot: &impl ExplainURL, item: SitemapItem) {
+        let verdict = Url::parse(item.loc())
+            .map_err(CheckError::ParseError)
+            .and_then(|url| bot.explain_url(&url));
+        match verdict {
+            Ok(verdict) if verdict.permission == Permission::Denied => {
+                self.denied.push(AuditedItem { item, verdict });
+            }
+            Ok(verdict) => self.allowed.push(AuditedItem { item, verdict }),
+            Err(err) => self.invalid.push((item, err)),
+        }
+    }
+
+    /// Returns `true` if no item of the sitemap is disallowed.
+    pub fn is_consistent(&self) -> bool {
+        self.denied.is_empty()
+    }
+
+    /// Returns the number of audited items.
+    pub fn len(&self) -> usize {
+        self.allowed.len() + self.denied.len() + self.invalid.len()
+    }
+
+    /// Returns `true` if no items have been audited.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,230 @@
+//! Checks URLs against `robots.txt` files from the command line.
+
+use std::{
+    io::{BufRead, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use nicebot::{MetaBot, Permission, SingleBot, TryAddRobots, Verdict, json_string};
+use url::Url;
+
+const USAGE: &str = "\
+Usage: nicebot [OPTIONS] <ROBOTS> [URL]...
+
+Checks whether URLs may be crawled according to robots.txt files.
+
+Arguments:
+  <ROBOTS>  A robots.txt file applying to every URL, or a directory of
+            robots.txt files named after their host (`example.com` or
+            `example.com.txt`)
+  [URL]...  The URLs to check. Read from stdin, one per line, if omitted.
+            Paths like `/about` are accepted if <ROBOTS> is a file.
+
+Options:
+  -a, --user-agent <NAME>  The user agent to check for [default: *]
+  -j, --json               Print one JSON object per line
+  -h, --help               Print this help
+
+Exit codes:
+  0  Every URL may be crawled
+  1  At least one URL is disallowed
+  2  An error occurred, e.g. an invalid URL or unreadable robots.txt";
+
+/// The exit code if every URL may be crawled.
+const EXIT_ALLOWED: u8 = 0;
+/// The exit code if at least one URL is disallowed.
+const EXIT_DENIED: u8 = 1;
+/// The exit code if an error occurred.
+const EXIT_ERROR: u8 = 2;
+
+struct Args {
+    robots: String,
+    user_agent: Option<String>,
+    json: bool,
+    urls: Vec<String>,
+}
+
+enum Robots {
+    Single(SingleBot),
+    Hosts(MetaBot),
+}
+
+impl Robots {
+    fn load(path: &Path, user_agent: Option<String>) -> Result<Self, String> {
+        if !path.is_dir() {
+            let file = std::fs::File::open(path)
+                .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+            let mut bot = SingleBot::new(user_agent);
+            bot += file;
+            return Ok(Robots::Single(bot));
+        }
+
+        let mut meta = MetaBot::new(user_agent);
+        let entries = std::fs::read_dir(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        for entry in entries {
+            let entry = entry.map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+            let host = name.strip_suffix(".txt").unwrap_or(name);
+            if !entry.path().is_file() || host.starts_with('.') {
+                continue;
+            }
+            let robots_txt = std::fs::read_to_string(entry.path())
+                .map_err(|err| format!("failed to read {}: {err}", entry.path().display()))?;
+            if !meta.try_add_robots(host, robots_txt) {
+                return Err(format!("{name} is not named after a host"));
+            }
+        }
+        Ok(Robots::Hosts(meta))
+    }
+
+    fn explain(&self, url: &str) -> Result<Verdict, String> {
+        match (self, Url::parse(url)) {
+            (Robots::Single(bot), Ok(parsed)) => Ok(bot.explain(parsed.path())),
+            (Robots::Single(bot), Err(_)) if url.starts_with('/') => Ok(bot.explain(url)),
+            (Robots::Hosts(meta), Ok(parsed)) => meta.explain(&parsed).map_err(|err| match err {
+                nicebot::CheckError::MissingHost => "URL doesn't contain host".to_owned(),
+                nicebot::CheckError::ParseError(err) => err.to_string(),
+            }),
+            (_, Err(err)) => Err(format!("invalid URL: {err}")),
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut robots = None;
+    let mut user_agent = None;
+    let mut json = false;
+    let mut urls = Vec::new();
+    let mut options = true;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" if options => return Ok(None),
+            "-j" | "--json" if options => json = true,
+            "-a" | "--user-agent" if options => {
+                let name = args.next().ok_or("missing value for --user-agent")?;
+                user_agent = Some(name);
+            }
+            "--" if options => options = false,
+            _ if options && arg.starts_with('-') && arg != "-" => {
+                if let Some(name) = arg.strip_prefix("--user-agent=") {
+                    user_agent = Some(name.to_owned());
+                } else {
+                    return Err(format!("unknown option {arg}"));
+                }
+            }
+            _ if robots.is_none() => robots = Some(arg),
+            _ => urls.push(arg),
+        }
+    }
+
+    let robots = robots.ok_or("missing <ROBOTS>")?;
+    // Every user agent matches the star group, so `*` is the same as none.
+    let user_agent = user_agent
+        .filter(|name| name != "*")
+        .map(|name| name.to_lowercase());
+    Ok(Some(Args {
+        robots,
+        user_agent,
+        json,
+        urls,
+    }))
+}
+
+fn permission_name(permission: Permission) -> &'static str {
+    match permission {
+        Permission::Allowed => "allowed",
+        Permission::Denied => "denied",
+        Permission::Unspecified => "unspecified",
+    }
+}
+
+fn format_result(url: &str, result: &Result<Verdict, String>, json: bool) -> String {
+    match (result, json) {
+        (Ok(verdict), false) => {
+            let permission = permission_name(verdict.permission);
+            match &verdict.rule {
+                Some(rule) => format!("{permission:<11} {url}  ({rule})"),
+                None => format!("{permission:<11} {url}"),
+            }
+        }
+        (Err(err), false) => format!("{:<11} {url}  ({err})", "error"),
+        (Ok(verdict), true) => {
+            let rule = verdict
+                .rule
+                .as_deref()
+                .map_or_else(|| "null".to_owned(), json_string);
+            format!(
+                r#"{{"url":{},"permission":"{}","rule":{rule}}}"#,
+                json_string(url),
+                permission_name(verdict.permission)
+            )
+        }
+        (Err(err), true) => format!(
+            r#"{{"url":{},"error":{}}}"#,
+            json_string(url),
+            json_string(err)
+        ),
+    }
+}
+
+fn run(args: Args) -> Result<u8, String> {
+    let robots = Robots::load(Path::new(&args.robots), args.user_agent)?;
+
+    let urls: Box<dyn Iterator<Item = Result<String, String>>> = if args.urls.is_empty() {
+        Box::new(
+            std::io::stdin()
+                .lock()
+                .lines()
+                .map(|line| line.map_err(|err| format!("failed to read stdin: {err}")))
+                .filter(|line| line.as_ref().is_ok_and(|line| !line.trim().is_empty())),
+        )
+    } else {
+        Box::new(args.urls.into_iter().map(Ok))
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    let mut exit = EXIT_ALLOWED;
+    for url in urls {
+        let url = url?;
+        let url = url.trim();
+        let result = robots.explain(url);
+        match &result {
+            Ok(verdict) if verdict.permission == Permission::Denied => {
+                exit = exit.max(EXIT_DENIED);
+            }
+            Ok(_) => {}
+            Err(_) => exit = EXIT_ERROR,
+        }
+        writeln!(stdout, "{}", format_result(url, &result, args.json))
+            .map_err(|err| format!("failed to write stdout: {err}"))?;
+    }
+    Ok(exit)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    match run(args) {
+        Ok(code) => ExitCode::from(code),
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+
+use url::{Host, Url};
+
+use crate::{AddRobots, CheckError, CheckURL, MetaBot, Permission, TryAddRobots};
+
+/// A [`MetaBot`] with bounded memory usage.
+///
+/// Once the number of hosts or the approximate number of bytes exceeds the configured limits,
+/// the least recently checked hosts are evicted. Hosts sharing an identical `robots.txt` are charged for it once,
+/// so its bytes are only freed with the last of them. Evicted hosts are recorded and can be retrieved
+/// with [`BoundedMetaBot::drain_evicted`], so their `robots.txt` can be fetched again when needed.
+#[derive(Clone, Debug)]
+pub struct BoundedMetaBot {
+    meta: MetaBot,
+    max_hosts: Option<usize>,
+    max_bytes: Option<usize>,
+    tick: u64,
+    recency: HashMap<Host<String>, u64>,
+    order: BTreeMap<u64, Host<String>>,
+    evicted: Vec<Host<String>>,
+}
+
+impl<T> AddRobots<T> for BoundedMetaBot
+where
+    MetaBot: AddRobots<T>,
+{
+    fn add_robots(&mut self, host: Host<String>, robots_txt: T) {
+        self.meta.add_robots(host.clone(), robots_txt);
+        self.touch(host);
+        self.evict();
+    }
+}
+
+impl<T, Q> TryAddRobots<T, Q> for BoundedMetaBot
+where
+    BoundedMetaBot: AddRobots<T>,
+    Q: AsRef<str>,
+{
+    fn try_add_robots(&mut self, host: Q, robots_txt: T) -> bool {
+        let Ok(parsed_host) = Host::parse(host.as_ref()) else {
+            return false;
+        };
+        self.add_robots(parsed_host, robots_txt);
+        true
+    }
+}
+
+impl BoundedMetaBot {
+    /// Creates a new [`BoundedMetaBot`] wrapping an empty [`MetaBot`].
+    /// `max_hosts` limits the number of hosts, `max_bytes` limits the approximate memory usage. `None` means unlimited.
+    pub fn new(
+        user_agent: Option<String>,
+        max_hosts: Option<usize>,
+        max_bytes: Option<usize>,
+    ) -> Self {
+        Self::with_meta(MetaBot::new(user_agent), max_hosts, max_bytes)
+    }
+
+    /// Creates a new [`BoundedMetaBot`] wrapping a configured [`MetaBot`], e.g. one with a profile, TTL or recogniser.
+    /// Hosts already known to `meta` count as used in no particular order, and are evicted right away if they exceed the limits.
+    pub fn with_meta(meta: MetaBot, max_hosts: Option<usize>, max_bytes: Option<usize>) -> Self {
+        let hosts: Vec<_> = meta.hosts().cloned().collect();
+        let mut bounded = Self {
+            meta,
+            max_hosts,
+            max_bytes,
+            tick: 0,
+            recency: HashMap::new(),
+            order: BTreeMap::new(),
+            evicted: Vec::new(),
+        };
+        for host in hosts {
+            bounded.touch(host);
+        }
+        bounded.evict();
+        bounded
+    }
+
+    /// Checks the permissions for a URL and marks its host as recently used.
+    /// # Errors
+    /// Will return `Err` if URL parsing fails or URL doesn't contain host.
+    pub fn check(&mut self, url: impl AsRef<str>) -> Result<Permission, CheckError> {
+        let url = Url::parse(url.as_ref()).map_err(CheckError::ParseError)?;
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        if self.recency.contains_key(&host) {
+            self.touch(host);
+        }
+        self.meta.check(url)
+    }
+
+    /// Checks the permissions for a URL without marking its host as recently used.
+    /// # Errors
+    /// Will return `Err` if URL parsing fails or URL doesn't contain host.
+    pub fn peek<T>(&self, url: T) -> Result<Permission, CheckError>
+    where
+        MetaBot: CheckURL<T>,
+    {
+        self.meta.check(url)
+    }
+
+    /// Forgets the `robots.txt` of a host. Removed hosts are not reported as evicted.
+    /// Returns `false` if no `robots.txt` was known for the host.
+    pub fn remove(&mut self, host: &Host<String>) -> bool {
+        if let Some(tick) = self.recency.remove(host) {
+            self.order.remove(&tick);
+        }
+        self.meta.remove(host)
+    }
+
+    /// Returns the hosts evicted since the last call, oldest first.
+    pub fn drain_evicted(&mut self) -> impl Iterator<Item = Host<String>> + '_ {
+        self.evicted.drain(..)
+    }
+
+    /// Returns the number of hosts with a known `robots.txt`.
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns `true` if no `robots.txt` is known for any host.
+    pub fn is_empty(&self) -> bool {
+        self.meta.is_empty()
+    }
+
+    /// Returns the approximate number of bytes occupied by the known `robots.txt`s, see [`MetaBot::approximate_size`].
+    pub fn approximate_size(&self) -> usize {
+        self.meta.approximate_size()
+    }
+
+    /// Gives access to the wrapped [`MetaBot`].
+    pub fn meta(&self) -> &MetaBot {
+        &self.meta
+    }
+
+    /// Marks a host as most recently used.
+    fn touch(&mut self, host: Host<String>) {
+        self.tick += 1;
+        if let Some(tick) = self.recency.insert(host.clone(), self.tick) {
+            self.order.remove(&tick);
+        }
+        self.order.insert(self.tick, host);
+    }
+
+    fn over_budget(&self) -> bool {
+        self.max_hosts.is_some_and(|max| self.recency.len() > max)
+            || self
+                .max_bytes
+                .is_some_and(|max| self.meta.approximate_size() > max)
+    }
+
+    fn evict(&mut self) {
+        // The most recently used host is never evicted, even if it exceeds the budget on its own.
+        while self.over_budget() && self.order.len() > 1 {
+            let Some((_, host)) = self.order.pop_first() else {
+                break;
+            };
+            self.recency.remove(&host);
+            self.meta.remove(&host);
+            self.evicted.push(host);
+        }
+    }
+}
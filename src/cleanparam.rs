@@ -0,0 +1,81 @@
+/// A `Clean-param` directive, listing query parameters which don't change the content of the pages under a path prefix,
+/// like `utm_source&utm_medium /articles/`.
+#[derive(Clone, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub struct CleanParam {
+    /// The names of the parameters, as written.
+    pub params: Vec<String>,
+    /// The prefix of the paths the parameters are removed from, where `*` matches any sequence of characters.
+    /// Empty if every path is covered.
+    pub prefix: String,
+}
+
+impl CleanParam {
+    /// Parses a `Clean-param` value like `ref&sid /forum/*.php`. Without prefix, every path is covered.
+    /// Returns `None` if no parameter is listed.
+    pub fn parse(val: &str) -> Option<Self> {
+        let mut parts = val.split_whitespace();
+        let params: Vec<String> = parts
+            .next()?
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(str::to_owned)
+            .collect();
+        if params.is_empty() {
+            return None;
+        }
+        let prefix = parts.next().unwrap_or_default().to_owned();
+        Some(Self { params, prefix })
+    }
+
+    /// Checks whether a path, without query, is covered by the prefix.
+    pub fn covers(&self, path: &str) -> bool {
+        let mut pieces = self.prefix.split('*');
+        let Some(rest) = path.strip_prefix(pieces.next().unwrap_or_default()) else {
+            return false;
+        };
+        let mut rest = rest;
+        for piece in pieces {
+            match rest.find(piece) {
+                Some(idx) => rest = &rest[idx + piece.len()..],
+                None => return false,
+            }
+        }
+        true
+    }
+
+    pub(crate) fn heap_size(&self) -> usize {
+        self.params.capacity() * size_of::<String>()
+            + self.params.iter().map(String::capacity).sum::<usize>()
+            + self.prefix.capacity()
+    }
+}
+
+/// Removes the query parameters listed by the directives covering a path, like `/maps?ll=1&z=2` to `/maps?z=2`.
+/// The remaining parameters and the fragment are kept in order.
+pub(crate) fn clean_path(clean_params: &[CleanParam], url: &str) -> String {
+    let (rest, fragment) = match url.find('#') {
+        Some(idx) => url.split_at(idx),
+        None => (url, ""),
+    };
+    let Some((path, query)) = rest.split_once('?') else {
+        return url.to_owned();
+    };
+    let covering: Vec<&CleanParam> = clean_params
+        .iter()
+        .filter(|clean_param| clean_param.covers(path))
+        .collect();
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split('=').next().unwrap_or_default();
+            !covering
+                .iter()
+                .any(|clean_param| clean_param.params.iter().any(|param| param == name))
+        })
+        .collect();
+    if kept.is_empty() {
+        format!("{path}{fragment}")
+    } else {
+        format!("{path}?{}{fragment}", kept.join("&"))
+    }
+}
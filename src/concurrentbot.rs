@@ -0,0 +1,279 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    ops::AddAssign,
+    sync::{Arc, PoisonError, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use url::{Host, Url};
+
+use crate::{
+    CheckError, CheckURL, DEFAULT_TTL, Permission, SingleBot, metabot::is_expired, ttl_from_headers,
+};
+
+const DEFAULT_SHARDS: usize = 64;
+
+type Shard = RwLock<HashMap<Host<String>, Entry>>;
+
+#[derive(Debug)]
+struct Entry {
+    bot: Arc<SingleBot>,
+    fetched_at: SystemTime,
+    ttl: Duration,
+}
+
+/// Used to check the `robots.txt`s of multiple Hosts from multiple threads.
+///
+/// Hosts are spread over independently locked shards, and locks are only held for the duration of a lookup or a swap.
+/// `robots.txt`s are parsed before any lock is taken, so adding a host never blocks checks on other hosts.
+/// Like [`crate::MetaBot`], every host remembers when its `robots.txt` was added and how long it stays valid.
+#[derive(Debug)]
+pub struct ConcurrentMetaBot {
+    shards: Box<[Shard]>,
+    hasher: RandomState,
+    user_agent: Option<String>,
+    ttl: Duration,
+}
+
+impl CheckURL<Url> for ConcurrentMetaBot {
+    fn check(&self, url: Url) -> Result<Permission, CheckError> {
+        if let Some(host) = url.host() {
+            if let Some(bot) = self.get(&host.to_owned()) {
+                Ok(bot.check(url.path()))
+            } else {
+                Ok(Permission::Unspecified)
+            }
+        } else {
+            Err(CheckError::MissingHost)
+        }
+    }
+}
+
+impl CheckURL<&str> for ConcurrentMetaBot {
+    fn check(&self, url: &str) -> Result<Permission, CheckError> {
+        match Url::parse(url) {
+            Ok(parsed) => self.check(parsed),
+            Err(err) => Err(CheckError::ParseError(err)),
+        }
+    }
+}
+
+impl CheckURL<&String> for ConcurrentMetaBot {
+    fn check(&self, url: &String) -> Result<Permission, CheckError> {
+        self.check(url.as_str())
+    }
+}
+
+impl CheckURL<String> for ConcurrentMetaBot {
+    fn check(&self, url: String) -> Result<Permission, CheckError> {
+        self.check(url.as_str())
+    }
+}
+
+impl Default for ConcurrentMetaBot {
+    fn default() -> Self {
+        ConcurrentMetaBot::new(None)
+    }
+}
+
+impl ConcurrentMetaBot {
+    /// Creates a new [`ConcurrentMetaBot`].
+    /// [`ConcurrentMetaBot`] is used to check multiple Hosts from multiple threads. If a single thread is enough, use [`crate::MetaBot`]
+    pub fn new(user_agent: Option<String>) -> Self {
+        Self::with_shards(user_agent, DEFAULT_SHARDS)
+    }
+
+    /// Creates a new [`ConcurrentMetaBot`] with a specific number of shards.
+    /// More shards reduce contention between threads adding `robots.txt`s.
+    pub fn with_shards(user_agent: Option<String>, shards: usize) -> Self {
+        let shards = (0..shards.max(1))
+            .map(|_| RwLock::new(HashMap::new()))
+            .collect();
+        Self {
+            shards,
+            hasher: RandomState::new(),
+            user_agent,
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    fn shard(&self, host: &Host<String>) -> &Shard {
+        let idx = self.hasher.hash_one(host) as usize % self.shards.len();
+        &self.shards[idx]
+    }
+
+    /// Sets the `robots.txt` for the specified host, replacing any previous one.
+    /// Returns the previous bot of the host.
+    pub fn replace_robots<T>(&self, host: Host<String>, robots_txt: T) -> Option<Arc<SingleBot>>
+    where
+        SingleBot: AddAssign<T>,
+    {
+        let mut bot = SingleBot::new(self.user_agent.clone());
+        bot += robots_txt;
+        self.insert(host, bot)
+    }
+
+    /// Sets the `robots.txt` for the specified host, replacing any previous one.
+    /// Returns `false` if parsing the host fails.
+    pub fn try_replace_robots<T>(&self, host: impl AsRef<str>, robots_txt: T) -> bool
+    where
+        SingleBot: AddAssign<T>,
+    {
+        let Ok(parsed_host) = Host::parse(host.as_ref()) else {
+            return false;
+        };
+        self.replace_robots(parsed_host, robots_txt);
+        true
+    }
+
+    /// Sets an already parsed bot for the specified host, replacing any previous one.
+    /// The bot is valid for the configured TTL from now on.
+    /// Returns the previous bot of the host.
+    pub fn insert(
+        &self,
+        host: Host<String>,
+        bot: impl Into<Arc<SingleBot>>,
+    ) -> Option<Arc<SingleBot>> {
+        self.insert_with_expiry(host, bot, SystemTime::now(), self.ttl)
+    }
+
+    /// Sets an already parsed bot for the specified host, valid for `ttl` from `fetched_at` on.
+    /// The TTL is capped by the configured one. Returns the previous bot of the host.
+    pub fn insert_with_expiry(
+        &self,
+        host: Host<String>,
+        bot: impl Into<Arc<SingleBot>>,
+        fetched_at: SystemTime,
+        ttl: Duration,
+    ) -> Option<Arc<SingleBot>> {
+        let entry = Entry {
+            bot: bot.into(),
+            fetched_at,
+            ttl: ttl.min(self.ttl),
+        };
+        self.shard(&host)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(host, entry)
+            .map(|previous| previous.bot)
+    }
+
+    /// Gets the bot of a host, even if it has expired.
+    pub fn get(&self, host: &Host<String>) -> Option<Arc<SingleBot>> {
+        self.shard(host)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(host)
+            .map(|entry| entry.bot.clone())
+    }
+
+    /// Gets the bot of a host unless it has expired by `now`.
+    pub fn get_fresh(&self, host: &Host<String>, now: SystemTime) -> Option<Arc<SingleBot>> {
+        self.shard(host)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(host)
+            .filter(|entry| !is_expired(entry.fetched_at, entry.ttl, now))
+            .map(|entry| entry.bot.clone())
+    }
+
+    /// Forgets the `robots.txt` of a host.
+    /// Returns the removed bot of the host.
+    pub fn remove(&self, host: &Host<String>) -> Option<Arc<SingleBot>> {
+        self.shard(host)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(host)
+            .map(|entry| entry.bot)
+    }
+
+    /// Sets the lifetime of newly added `robots.txt`s. It is also the upper bound for lifetimes derived from HTTP headers.
+    /// Defaults to [`DEFAULT_TTL`].
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    /// Sets when the `robots.txt` of a host was fetched and how long it stays valid.
+    /// Returns `false` if no `robots.txt` is known for the host.
+    pub fn set_expiry(&self, host: &Host<String>, fetched_at: SystemTime, ttl: Duration) -> bool {
+        let mut shard = self
+            .shard(host)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let Some(entry) = shard.get_mut(host) else {
+            return false;
+        };
+        entry.fetched_at = fetched_at;
+        entry.ttl = ttl.min(self.ttl);
+        true
+    }
+
+    /// Sets the expiry of a host from the `Cache-Control` and `Expires` headers of the response its `robots.txt` was fetched with.
+    /// Falls back to the configured TTL if neither header specifies a lifetime.
+    /// Returns `false` if no `robots.txt` is known for the host.
+    pub fn set_expiry_from_headers(
+        &self,
+        host: &Host<String>,
+        fetched_at: SystemTime,
+        cache_control: Option<&str>,
+        expires: Option<&str>,
+    ) -> bool {
+        let ttl = ttl_from_headers(cache_control, expires, fetched_at).unwrap_or(self.ttl);
+        self.set_expiry(host, fetched_at, ttl)
+    }
+
+    /// Returns when the `robots.txt` of a host expires.
+    pub fn expires_at(&self, host: &Host<String>) -> Option<SystemTime> {
+        self.shard(host)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(host)
+            .map(|entry| entry.fetched_at + entry.ttl)
+    }
+
+    /// Checks whether the `robots.txt` of a host has expired by `now`.
+    /// Returns `None` if no `robots.txt` is known for the host.
+    pub fn is_stale(&self, host: &Host<String>, now: SystemTime) -> Option<bool> {
+        self.shard(host)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(host)
+            .map(|entry| is_expired(entry.fetched_at, entry.ttl, now))
+    }
+
+    /// Lists all hosts whose `robots.txt` has expired by `now` and should be fetched again.
+    /// Shards are locked one after the other, so hosts changed meanwhile may be missed.
+    pub fn stale_hosts(&self, now: SystemTime) -> Vec<Host<String>> {
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                shard
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .iter()
+                    .filter(|(_, entry)| is_expired(entry.fetched_at, entry.ttl, now))
+                    .map(|(host, _)| host.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the number of hosts with a known `robots.txt`.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap_or_else(PoisonError::into_inner).len())
+            .sum()
+    }
+
+    /// Returns `true` if no `robots.txt` is known for any host.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the user agent new bots are created for.
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+use crate::{
+    Correction, ParseProfile, Recogniser,
+    singlebot::{allows_spaces, is_directive, is_global, parse_pair_str, strip_comment_str},
+};
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// The line is used, but likely doesn't do what was intended, or it is ignored but may be meant for other parsers.
+    Warning,
+    /// The line is ignored by the parser.
+    Error,
+}
+
+/// The kind of problem a [`Diagnostic`] reports.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum DiagnosticKind {
+    /// The line has no `:` separating directive and value.
+    MissingColon,
+    /// The directive isn't known to the parser.
+    UnknownDirective,
+    /// The value contains spaces.
+    ValueWithSpaces,
+    /// A rule appears before any `User-agent` line, so it belongs to no group, unless the profile applies it like a `*` group.
+    RuleOutsideGroup,
+    /// The location of a `Sitemap` isn't an absolute URL.
+    RelativeSitemap,
+    /// The pattern of an `Allow` or `Disallow` rule starts with neither `/` nor `*`.
+    /// Depending on the profile, it never matches, is ignored or gets a `/` prepended.
+    PatternWithoutSlash,
+    /// The directive is misspelled, but a lenient [`Recogniser`] reads it as the directive it stands for.
+    MisspelledDirective,
+}
+
+impl DiagnosticKind {
+    /// Returns how serious problems of this kind usually are. Lines corrected by a [`Recogniser`] are always warnings,
+    /// and a [`ParseProfile`] may ignore or accept a line where others don't.
+    pub fn severity(&self) -> Severity {
+        match self {
+            DiagnosticKind::UnknownDirective
+            | DiagnosticKind::MisspelledDirective
+            | DiagnosticKind::RelativeSitemap
+            | DiagnosticKind::PatternWithoutSlash => Severity::Warning,
+            DiagnosticKind::MissingColon
+            | DiagnosticKind::ValueWithSpaces
+            | DiagnosticKind::RuleOutsideGroup => Severity::Error,
+        }
+    }
+}
+
+/// A problem found on a line of a `robots.txt`.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Diagnostic {
+    /// The number of the line, starting at 1.
+    pub line: usize,
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// The kind of problem.
+    pub kind: DiagnosticKind,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(line: usize, kind: DiagnosticKind, message: String) -> Self {
+        Self::with_severity(line, kind.severity(), kind, message)
+    }
+
+    fn with_severity(
+        line: usize,
+        severity: Severity,
+        kind: DiagnosticKind,
+        message: String,
+    ) -> Self {
+        Self {
+            line,
+            severity,
+            kind,
+            message,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "line {}: {severity}: {}", self.line, self.message)
+    }
+}
+
+/// Reports every line of a `robots.txt` which is ignored by the parser or looks suspicious, in order of appearance,
+/// for the default [`ParseProfile`].
+pub fn diagnose(robots_txt: &str) -> Vec<Diagnostic> {
+    diagnose_with(robots_txt, ParseProfile::default())
+}
+
+/// Reports every line of a `robots.txt` like [`diagnose`], as the crawlers of a [`ParseProfile`] read it.
+/// Every correction made to read a line is reported as a warning.
+pub fn diagnose_with(robots_txt: &str, profile: ParseProfile) -> Vec<Diagnostic> {
+    diagnose_with_recogniser(robots_txt, profile, &profile.recogniser())
+}
+
+/// Reports every line of a `robots.txt` like [`diagnose_with`], for a bot recognising directives with `recogniser`
+/// instead of the one of the profile, see [`crate::SingleBot::set_recogniser`].
+pub fn diagnose_with_recogniser(
+    robots_txt: &str,
+    profile: ParseProfile,
+    recogniser: &Recogniser,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut in_group = false;
+
+    for (idx, line) in robots_txt.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = strip_comment_str(line).trim();
+        let Some(recognised) = recogniser.recognise(line) else {
+            if let Some(diagnostic) = explain_ignored(line_number, line) {
+                diagnostics.push(diagnostic);
+            }
+            continue;
+        };
+
+        let op = recognised.directive.as_str();
+        let val = recognised.value.as_str();
+        let mut op_raw = parse_pair_str(line).map_or(op, |(op, _)| op.trim());
+        for correction in &recognised.corrections {
+            let (kind, message) = match correction {
+                Correction::Typo(_) => (
+                    DiagnosticKind::MisspelledDirective,
+                    format!("{correction}, it is read as `{op}`"),
+                ),
+                Correction::MissingColon => {
+                    op_raw = line.split_whitespace().next().unwrap_or(op);
+                    (
+                        DiagnosticKind::MissingColon,
+                        format!("{correction}, it is read as `{op}`"),
+                    )
+                }
+                Correction::Truncated(_) => {
+                    (DiagnosticKind::ValueWithSpaces, correction.to_string())
+                }
+                Correction::Spaces => (
+                    DiagnosticKind::ValueWithSpaces,
+                    format!("the value `{val}` contains spaces, it is kept as written"),
+                ),
+            };
+            diagnostics.push(Diagnostic {
+                line: line_number,
+                severity: Severity::Warning,
+                kind,
+                message,
+            });
+        }
+
+        match op {
+            "user-agent" => in_group = true,
+            "sitemap" if !val.contains("://") => {
+                diagnostics.push(Diagnostic::new(
+                    line_number,
+                    DiagnosticKind::RelativeSitemap,
+                    format!("the sitemap `{val}` is not an absolute URL"),
+                ));
+            }
+            _ if is_global(op) => {}
+            _ if !in_group && profile.keeps_leading_rules() => {
+                diagnostics.push(Diagnostic::with_severity(
+                    line_number,
+                    Severity::Warning,
+                    DiagnosticKind::RuleOutsideGroup,
+                    format!(
+                        "`{op_raw}` appears before any `User-agent`, it applies to every user agent like a `*` group"
+                    ),
+                ));
+            }
+            _ if !in_group => {
+                diagnostics.push(Diagnostic::new(
+                    line_number,
+                    DiagnosticKind::RuleOutsideGroup,
+                    format!("`{op_raw}` appears before any `User-agent`, the line is ignored"),
+                ));
+            }
+            "allow" | "disallow" if !val.is_empty() && !val.starts_with(['/', '*']) => {
+                let diagnostic = match profile.prepare((op.to_owned(), val.to_owned())) {
+                    None => Diagnostic::with_severity(
+                        line_number,
+                        Severity::Error,
+                        DiagnosticKind::PatternWithoutSlash,
+                        format!("the pattern `{val}` doesn't start with `/`, the line is ignored"),
+                    ),
+                    Some((_, pattern)) if pattern != val => Diagnostic::new(
+                        line_number,
+                        DiagnosticKind::PatternWithoutSlash,
+                        format!(
+                            "the pattern `{val}` doesn't start with `/`, it is read as `{pattern}`"
+                        ),
+                    ),
+                    Some(_) => Diagnostic::new(
+                        line_number,
+                        DiagnosticKind::PatternWithoutSlash,
+                        format!("the pattern `{val}` doesn't start with `/`, so it never matches"),
+                    ),
+                };
+                diagnostics.push(diagnostic);
+            }
+            _ => {}
+        }
+    }
+
+    diagnostics
+}
+
+/// Explains why a line without comment was not recognised. Returns `None` for empty lines.
+fn explain_ignored(line_number: usize, line: &str) -> Option<Diagnostic> {
+    if line.is_empty() {
+        return None;
+    }
+    let Some((op, val)) = parse_pair_str(line) else {
+        return Some(Diagnostic::new(
+            line_number,
+            DiagnosticKind::MissingColon,
+            format!("`{line}` is missing a `:` between directive and value, the line is ignored"),
+        ));
+    };
+
+    let op_raw = op.trim();
+    let op = op_raw.to_lowercase();
+    let val = val.trim();
+    if !is_directive(&op) {
+        return Some(Diagnostic::new(
+            line_number,
+            DiagnosticKind::UnknownDirective,
+            format!("unknown directive `{op_raw}`, the line is ignored"),
+        ));
+    }
+    (!allows_spaces(&op) && val.contains(' ')).then(|| {
+        Diagnostic::new(
+            line_number,
+            DiagnosticKind::ValueWithSpaces,
+            format!("the value `{val}` contains spaces, the line is ignored"),
+        )
+    })
+}
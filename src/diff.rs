@@ -0,0 +1,246 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    time::Duration,
+};
+
+use crate::{
+    ContentSignals, Group, Permission, RequestRate, RobotsTxt, Verdict, singlebot::decode_pattern,
+    trie::witnesses,
+};
+
+/// A path whose permission differs between two versions of a `robots.txt`.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct PermissionChange {
+    /// An example path affected by the change.
+    /// Characters matched by a wildcard or following the end of a pattern are written as `\u{FFFD}`.
+    pub path: String,
+    /// The verdict of the old version.
+    pub before: Verdict,
+    /// The verdict of the new version.
+    pub after: Verdict,
+}
+
+/// A group present in both versions of a `robots.txt` whose directives changed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GroupChange {
+    /// The group in the old version.
+    pub before: Group,
+    /// The group in the new version.
+    pub after: Group,
+}
+
+/// The differences between two versions of a `robots.txt`, as seen by one user agent.
+///
+/// Groups are matched by their user agents, ignoring case and order.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RobotsDiff {
+    /// Groups only found in the new version.
+    pub added_groups: Vec<Group>,
+    /// Groups only found in the old version.
+    pub removed_groups: Vec<Group>,
+    /// Groups found in both versions with different rules or delays.
+    pub changed_groups: Vec<GroupChange>,
+    /// Sitemaps only listed by the new version.
+    pub added_sitemaps: Vec<String>,
+    /// Sitemaps only listed by the old version.
+    pub removed_sitemaps: Vec<String>,
+    /// Paths which become crawlable or stop being crawlable for the user agent, ordered by path.
+    pub permission_changes: Vec<PermissionChange>,
+    /// The `Crawl-delay` for the user agent before and after, if it changed.
+    pub crawl_delay: Option<(Option<Duration>, Option<Duration>)>,
+    /// The `Request-rate` for the user agent before and after, if it changed.
+    pub request_rate: Option<(Option<RequestRate>, Option<RequestRate>)>,
+    /// The `Content-Signal` preferences for the user agent before and after, if they changed.
+    pub content_signals: Option<(Option<ContentSignals>, Option<ContentSignals>)>,
+}
+
+fn agents_key(group: &Group) -> BTreeSet<String> {
+    group
+        .user_agents
+        .iter()
+        .map(|agent| agent.to_lowercase())
+        .collect()
+}
+
+/// Compares the directives of two groups, ignoring line numbers.
+fn same_directives(old: &Group, new: &Group) -> bool {
+    let rules = |group: &Group| {
+        group
+            .rules
+            .iter()
+            .map(|rule| (rule.permission, rule.pattern.clone()))
+            .collect::<Vec<_>>()
+    };
+    rules(old) == rules(new)
+        && old.crawl_delay == new.crawl_delay
+        && old.request_rate == new.request_rate
+        && old.content_signals == new.content_signals
+}
+
+impl RobotsDiff {
+    /// Compares two versions of a `robots.txt` for a user agent.
+    /// Without user agent, permissions are compared for the `*` groups.
+    pub fn new(old: &RobotsTxt, new: &RobotsTxt, user_agent: Option<&str>) -> Self {
+        let mut diff = RobotsDiff::default();
+
+        for group in &new.groups {
+            let key = agents_key(group);
+            match old.groups.iter().find(|old| agents_key(old) == key) {
+                None => diff.added_groups.push(group.clone()),
+                Some(old) if !same_directives(old, group) => {
+                    diff.changed_groups.push(GroupChange {
+                        before: old.clone(),
+                        after: group.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed_groups = old
+            .groups
+            .iter()
+            .filter(|group| {
+                let key = agents_key(group);
+                !new.groups.iter().any(|new| agents_key(new) == key)
+            })
+            .cloned()
+            .collect();
+
+        diff.added_sitemaps = new
+            .sitemaps
+            .iter()
+            .filter(|sitemap| !old.sitemaps.contains(sitemap))
+            .cloned()
+            .collect();
+        diff.removed_sitemaps = old
+            .sitemaps
+            .iter()
+            .filter(|sitemap| !new.sitemaps.contains(sitemap))
+            .cloned()
+            .collect();
+
+        let old_bot = old.bot(user_agent);
+        let new_bot = new.bot(user_agent);
+
+        // Every rule which may decide a path in either version contributes example paths, including paths
+        // reaching past its end. The first of them whose permission changes is reported.
+        let samples = old
+            .groups_for(user_agent)
+            .into_iter()
+            .chain(new.groups_for(user_agent))
+            .flat_map(|group| &group.rules)
+            .map(|rule| witnesses(&decode_pattern(rule.pattern.clone())))
+            .chain(std::iter::once(vec!["/".to_owned()]));
+        let mut changes = BTreeMap::new();
+        for paths in samples {
+            for path in paths {
+                let before = old_bot.explain(&path);
+                let after = new_bot.explain(&path);
+                let denied = |verdict: &Verdict| verdict.permission == Permission::Denied;
+                if denied(&before) != denied(&after) {
+                    changes.insert(
+                        path.clone(),
+                        PermissionChange {
+                            path,
+                            before,
+                            after,
+                        },
+                    );
+                    break;
+                }
+            }
+        }
+        diff.permission_changes = changes.into_values().collect();
+
+        if old_bot.crawl_delay() != new_bot.crawl_delay() {
+            diff.crawl_delay = Some((old_bot.crawl_delay(), new_bot.crawl_delay()));
+        }
+        if old_bot.request_rate() != new_bot.request_rate() {
+            diff.request_rate = Some((old_bot.request_rate(), new_bot.request_rate()));
+        }
+        if old_bot.content_signals() != new_bot.content_signals() {
+            diff.content_signals = Some((old_bot.content_signals(), new_bot.content_signals()));
+        }
+
+        diff
+    }
+
+    /// Returns `true` if the versions don't differ.
+    pub fn is_empty(&self) -> bool {
+        self.added_groups.is_empty()
+            && self.removed_groups.is_empty()
+            && self.changed_groups.is_empty()
+            && self.added_sitemaps.is_empty()
+            && self.removed_sitemaps.is_empty()
+            && self.permission_changes.is_empty()
+            && self.crawl_delay.is_none()
+            && self.request_rate.is_none()
+            && self.content_signals.is_none()
+    }
+}
+
+fn describe(verdict: &Verdict) -> String {
+    let permission = match verdict.permission {
+        Permission::Allowed => "allowed",
+        Permission::Denied => "denied",
+        Permission::Unspecified => "unspecified",
+    };
+    match &verdict.rule {
+        Some(rule) => format!("{permission} by `{rule}`"),
+        None => permission.to_owned(),
+    }
+}
+
+impl Display for RobotsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for group in &self.added_groups {
+            writeln!(f, "+ group {}", group.user_agents.join(", "))?;
+        }
+        for group in &self.removed_groups {
+            writeln!(f, "- group {}", group.user_agents.join(", "))?;
+        }
+        for change in &self.changed_groups {
+            writeln!(f, "~ group {}", change.after.user_agents.join(", "))?;
+        }
+        for sitemap in &self.added_sitemaps {
+            writeln!(f, "+ sitemap {sitemap}")?;
+        }
+        for sitemap in &self.removed_sitemaps {
+            writeln!(f, "- sitemap {sitemap}")?;
+        }
+        for change in &self.permission_changes {
+            writeln!(
+                f,
+                "~ {}: {} -> {}",
+                change.path,
+                describe(&change.before),
+                describe(&change.after)
+            )?;
+        }
+        if let Some((before, after)) = self.crawl_delay {
+            writeln!(f, "~ crawl-delay: {before:?} -> {after:?}")?;
+        }
+        if let Some((before, after)) = self.request_rate {
+            let rate = |rate: Option<RequestRate>| {
+                rate.map_or_else(
+                    || "None".to_owned(),
+                    |rate| format!("{}/{}s", rate.requests, rate.per.as_secs()),
+                )
+            };
+            writeln!(f, "~ request-rate: {} -> {}", rate(before), rate(after))?;
+        }
+        if let Some((before, after)) = self.content_signals {
+            let signals = |signals: Option<ContentSignals>| {
+                signals.map_or_else(|| "None".to_owned(), |signals| signals.to_string())
+            };
+            writeln!(
+                f,
+                "~ content-signal: {} -> {}",
+                signals(before),
+                signals(after)
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+use std::{
+    collections::HashMap,
+    ops::AddAssign,
+    sync::{Arc, Condvar, Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+#[cfg(feature = "async")]
+use async_trait::async_trait;
+use url::{Host, Url};
+
+use crate::{AgentBot, CheckError, CheckURL, ConcurrentMetaBot, Permission, SingleBot};
+
+/// The result of fetching a `robots.txt`.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum FetchOutcome {
+    /// The `robots.txt` was retrieved.
+    Found(String),
+    /// The host does not provide a `robots.txt` (e.g. HTTP status 4xx), so every path is allowed.
+    Unavailable,
+    /// The `robots.txt` could not be retrieved (e.g. HTTP status 5xx or network errors), so every path is denied.
+    Unreachable,
+}
+
+impl FetchOutcome {
+    /// Turns the outcome into a bot for the given user agent.
+    pub fn into_bot(self, user_agent: Option<String>) -> SingleBot {
+        let mut bot = SingleBot::new(user_agent);
+        bot += self;
+        bot
+    }
+}
+
+impl AddAssign<FetchOutcome> for SingleBot {
+    fn add_assign(&mut self, rhs: FetchOutcome) {
+        match rhs {
+            FetchOutcome::Found(robots_txt) => *self += robots_txt,
+            FetchOutcome::Unavailable => {}
+            FetchOutcome::Unreachable => self.deny_unspecified(),
+        }
+    }
+}
+
+impl AddAssign<FetchOutcome> for AgentBot {
+    fn add_assign(&mut self, rhs: FetchOutcome) {
+        match rhs {
+            FetchOutcome::Found(robots_txt) => *self += robots_txt,
+            FetchOutcome::Unavailable => {}
+            FetchOutcome::Unreachable => self.deny_unspecified(),
+        }
+    }
+}
+
+/// Retrieves `robots.txt`s.
+pub trait RobotsFetcher {
+    /// Fetches the `robots.txt` located at `robots_url`.
+    fn fetch(&self, robots_url: &Url) -> FetchOutcome;
+}
+
+/// Retrieves `robots.txt`s asynchronously.
+#[cfg(feature = "async")]
+#[async_trait]
+pub trait AsyncRobotsFetcher {
+    /// Fetches the `robots.txt` located at `robots_url`.
+    async fn fetch_async(&self, robots_url: &Url) -> FetchOutcome;
+}
+
+/// Returns the location of the `robots.txt` responsible for a URL.
+/// Returns `None` if the URL doesn't contain a host.
+pub fn robots_url(url: &Url) -> Option<Url> {
+    url.host()?;
+    let mut robots = url.clone();
+    robots.set_path("/robots.txt");
+    robots.set_query(None);
+    robots.set_fragment(None);
+    // Credentials are part of the request, not of the resource.
+    let _ = robots.set_username("");
+    let _ = robots.set_password(None);
+    Some(robots)
+}
+
+/// How long an unreachable `robots.txt` denies every path before it is fetched again, unless configured otherwise.
+pub const DEFAULT_RETRY_TTL: Duration = Duration::from_secs(60);
+
+/// A [`ConcurrentMetaBot`] which fetches unknown and expired `robots.txt`s on demand.
+///
+/// Concurrent checks of a host whose `robots.txt` is not known yet result in a single fetch;
+/// all other callers wait for its outcome instead of fetching on their own.
+/// Fetched `robots.txt`s are kept for the TTL of the [`ConcurrentMetaBot`],
+/// while unreachable ones deny every path for the shorter retry TTL.
+#[derive(Debug)]
+pub struct FetchingMetaBot<F> {
+    bots: ConcurrentMetaBot,
+    fetcher: F,
+    flights: Mutex<HashMap<Host<String>, Arc<Flight>>>,
+    retry_ttl: Duration,
+}
+
+#[derive(Debug)]
+enum Landing {
+    Pending(Vec<std::task::Waker>),
+    Landed(Arc<SingleBot>),
+    Abandoned,
+}
+
+/// A fetch in progress which other callers may wait for.
+#[derive(Debug)]
+struct Flight {
+    landing: Mutex<Landing>,
+    landed: Condvar,
+}
+
+impl Flight {
+    fn new() -> Self {
+        Self {
+            landing: Mutex::new(Landing::Pending(Vec::new())),
+            landed: Condvar::new(),
+        }
+    }
+
+    fn finish(&self, landing: Landing) {
+        let mut current = self.landing.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Landing::Pending(wakers) = std::mem::replace(&mut *current, landing) {
+            wakers.into_iter().for_each(std::task::Waker::wake);
+        }
+        self.landed.notify_all();
+    }
+
+    /// Blocks until the flight is over. Returns `None` if it was abandoned.
+    fn wait(&self) -> Option<Arc<SingleBot>> {
+        let mut landing = self.landing.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match &*landing {
+                Landing::Pending(_) => {
+                    landing = self
+                        .landed
+                        .wait(landing)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+                Landing::Landed(bot) => return Some(bot.clone()),
+                Landing::Abandoned => return None,
+            }
+        }
+    }
+}
+
+/// Resolves once a flight is over. Resolves to `None` if it was abandoned.
+#[cfg(feature = "async")]
+struct FlightFuture(Arc<Flight>);
+
+#[cfg(feature = "async")]
+impl std::future::Future for FlightFuture {
+    type Output = Option<Arc<SingleBot>>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let mut landing = self
+            .0
+            .landing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match &mut *landing {
+            Landing::Pending(wakers) => {
+                if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                std::task::Poll::Pending
+            }
+            Landing::Landed(bot) => std::task::Poll::Ready(Some(bot.clone())),
+            Landing::Abandoned => std::task::Poll::Ready(None),
+        }
+    }
+}
+
+/// Held by the caller performing a fetch. Abandons the flight if dropped before landing,
+/// so waiting callers retry instead of waiting forever.
+struct FlightGuard<'a> {
+    flights: &'a Mutex<HashMap<Host<String>, Arc<Flight>>>,
+    host: Host<String>,
+    flight: Arc<Flight>,
+    landed: bool,
+}
+
+impl FlightGuard<'_> {
+    fn land(mut self, bot: Arc<SingleBot>) {
+        self.landed = true;
+        self.leave();
+        self.flight.finish(Landing::Landed(bot));
+    }
+
+    fn leave(&self) {
+        let mut flights = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+        if flights
+            .get(&self.host)
+            .is_some_and(|flight| Arc::ptr_eq(flight, &self.flight))
+        {
+            flights.remove(&self.host);
+        }
+    }
+}
+
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        if !self.landed {
+            self.leave();
+            self.flight.finish(Landing::Abandoned);
+        }
+    }
+}
+
+enum Role<'a> {
+    Cached(Arc<SingleBot>),
+    Leader(FlightGuard<'a>),
+    Follower(Arc<Flight>),
+}
+
+impl<F> CheckURL<Url> for FetchingMetaBot<F>
+where
+    F: RobotsFetcher,
+{
+    fn check(&self, url: Url) -> Result<Permission, CheckError> {
+        let bot = self.lookup(&url)?;
+        Ok(bot.check(url.path()))
+    }
+}
+
+impl<F> CheckURL<&str> for FetchingMetaBot<F>
+where
+    F: RobotsFetcher,
+{
+    fn check(&self, url: &str) -> Result<Permission, CheckError> {
+        match Url::parse(url) {
+            Ok(parsed) => self.check(parsed),
+            Err(err) => Err(CheckError::ParseError(err)),
+        }
+    }
+}
+
+impl<F> CheckURL<&String> for FetchingMetaBot<F>
+where
+    F: RobotsFetcher,
+{
+    fn check(&self, url: &String) -> Result<Permission, CheckError> {
+        self.check(url.as_str())
+    }
+}
+
+impl<F> CheckURL<String> for FetchingMetaBot<F>
+where
+    F: RobotsFetcher,
+{
+    fn check(&self, url: String) -> Result<Permission, CheckError> {
+        self.check(url.as_str())
+    }
+}
+
+/// Allows for checking the permissions for a URL asynchronously.
+#[cfg(feature = "async")]
+#[async_trait]
+pub trait CheckURLAsync<T> {
+    /// Checks the permissions for a URL.
+    /// # Errors
+    /// Will return `Err` if URL parsing fails or URL doesn't contain host.
+    async fn check_async(&self, url: T) -> Result<Permission, CheckError>
+    where
+        T: 'async_trait + Send;
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<F> CheckURLAsync<Url> for FetchingMetaBot<F>
+where
+    F: AsyncRobotsFetcher + Send + Sync,
+{
+    async fn check_async(&self, url: Url) -> Result<Permission, CheckError> {
+        let bot = self.lookup_async(&url).await?;
+        Ok(bot.check(url.path()))
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<'a, F> CheckURLAsync<&'a str> for FetchingMetaBot<F>
+where
+    F: AsyncRobotsFetcher + Send + Sync,
+{
+    async fn check_async(&self, url: &'a str) -> Result<Permission, CheckError>
+    where
+        'a: 'async_trait,
+    {
+        match Url::parse(url) {
+            Ok(parsed) => self.check_async(parsed).await,
+            Err(err) => Err(CheckError::ParseError(err)),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<F> CheckURLAsync<String> for FetchingMetaBot<F>
+where
+    F: AsyncRobotsFetcher + Send + Sync,
+{
+    async fn check_async(&self, url: String) -> Result<Permission, CheckError> {
+        self.check_async(url.as_str()).await
+    }
+}
+
+impl<F> FetchingMetaBot<F> {
+    /// Creates a new [`FetchingMetaBot`] which fetches `robots.txt`s with `fetcher`.
+    pub fn new(user_agent: Option<String>, fetcher: F) -> Self {
+        Self::with_bots(ConcurrentMetaBot::new(user_agent), fetcher)
+    }
+
+    /// Creates a new [`FetchingMetaBot`] on top of already known `robots.txt`s.
+    pub fn with_bots(bots: ConcurrentMetaBot, fetcher: F) -> Self {
+        Self {
+            bots,
+            fetcher,
+            flights: Mutex::new(HashMap::new()),
+            retry_ttl: DEFAULT_RETRY_TTL,
+        }
+    }
+
+    /// Sets how long an unreachable `robots.txt` denies every path before it is fetched again.
+    /// Defaults to [`DEFAULT_RETRY_TTL`].
+    pub fn set_retry_ttl(&mut self, ttl: Duration) {
+        self.retry_ttl = ttl;
+    }
+
+    /// Gives access to the known `robots.txt`s, e.g. to add or invalidate hosts.
+    pub fn bots(&self) -> &ConcurrentMetaBot {
+        &self.bots
+    }
+
+    /// Gives access to the fetcher.
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    fn role(&self, host: &Host<String>) -> Role<'_> {
+        let mut flights = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(flight) = flights.get(host) {
+            return Role::Follower(flight.clone());
+        }
+        // The bot may have landed since the caller's lookup, as leaders store it before leaving.
+        if let Some(bot) = self.bots.get_fresh(host, SystemTime::now()) {
+            return Role::Cached(bot);
+        }
+        let flight = Arc::new(Flight::new());
+        flights.insert(host.clone(), flight.clone());
+        Role::Leader(FlightGuard {
+            flights: &self.flights,
+            host: host.clone(),
+            flight,
+            landed: false,
+        })
+    }
+
+    /// Turns an outcome into a bot and remembers it for the host.
+    /// [`FetchOutcome::Unreachable`] is likely transient, so its bot expires after the retry TTL.
+    fn store(&self, host: &Host<String>, outcome: FetchOutcome) -> Arc<SingleBot> {
+        let user_agent = self.bots.user_agent().map(str::to_owned);
+        let ttl = match outcome {
+            FetchOutcome::Unreachable => self.retry_ttl,
+            _ => Duration::MAX,
+        };
+        let bot = Arc::new(outcome.into_bot(user_agent));
+        self.bots
+            .insert_with_expiry(host.clone(), bot.clone(), SystemTime::now(), ttl);
+        bot
+    }
+}
+
+impl<F> FetchingMetaBot<F>
+where
+    F: RobotsFetcher,
+{
+    /// Gets the bot responsible for a URL, fetching its `robots.txt` if it is not known yet or has expired.
+    /// # Errors
+    /// Will return `Err` if the URL doesn't contain host.
+    pub fn lookup(&self, url: &Url) -> Result<Arc<SingleBot>, CheckError> {
+        let robots = robots_url(url).ok_or(CheckError::MissingHost)?;
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        loop {
+            if let Some(bot) = self.bots.get_fresh(&host, SystemTime::now()) {
+                return Ok(bot);
+            }
+            match self.role(&host) {
+                Role::Cached(bot) => return Ok(bot),
+                Role::Leader(guard) => {
+                    let bot = self.store(&host, self.fetcher.fetch(&robots));
+                    guard.land(bot.clone());
+                    return Ok(bot);
+                }
+                Role::Follower(flight) => {
+                    if let Some(bot) = flight.wait() {
+                        return Ok(bot);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<F> FetchingMetaBot<F>
+where
+    F: AsyncRobotsFetcher,
+{
+    /// Gets the bot responsible for a URL, fetching its `robots.txt` if it is not known yet or has expired.
+    /// # Errors
+    /// Will return `Err` if the URL doesn't contain host.
+    pub async fn lookup_async(&self, url: &Url) -> Result<Arc<SingleBot>, CheckError> {
+        let robots = robots_url(url).ok_or(CheckError::MissingHost)?;
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        loop {
+            if let Some(bot) = self.bots.get_fresh(&host, SystemTime::now()) {
+                return Ok(bot);
+            }
+            match self.role(&host) {
+                Role::Cached(bot) => return Ok(bot),
+                Role::Leader(guard) => {
+                    let outcome = self.fetcher.fetch_async(&robots).await;
+                    let bot = self.store(&host, outcome);
+                    guard.land(bot.clone());
+                    return Ok(bot);
+                }
+                Role::Follower(flight) => {
+                    if let Some(bot) = FlightFuture(flight).await {
+                        return Ok(bot);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+
+/// Elements whose content is text rather than markup.
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["script", "style", "textarea", "title"];
+
+/// A start tag of an HTML document.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Tag<'a> {
+    /// The name of the element, in lowercase.
+    pub(crate) name: String,
+    /// The attributes with their names in lowercase and their values decoded.
+    pub(crate) attributes: Vec<(String, Cow<'a, str>)>,
+}
+
+impl Tag<'_> {
+    /// Returns the value of the first attribute of a name, which must be lowercase.
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_ref())
+    }
+}
+
+/// Iterates over the start tags of an HTML document, skipping comments and the content of scripts and styles.
+///
+/// This is a lenient scanner rather than a full HTML parser, which is enough to find `<meta>`, `<base>` and `<a>` tags.
+pub(crate) fn tags(html: &str) -> Tags<'_> {
+    Tags { html, pos: 0 }
+}
+
+pub(crate) struct Tags<'a> {
+    html: &'a str,
+    pos: usize,
+}
+
+impl<'a> Iterator for Tags<'a> {
+    type Item = Tag<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.html[self.pos..];
+            let start = rest.find('<')?;
+            let rest = &rest[start..];
+            self.pos += start;
+
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                self.pos += comment.find("-->").map_or(rest.len(), |end| end + 7);
+                continue;
+            }
+            let bytes = rest.as_bytes();
+            if !bytes.get(1).is_some_and(u8::is_ascii_alphabetic) {
+                // Doctypes, processing instructions and end tags carry nothing of interest.
+                self.pos += if matches!(bytes.get(1), Some(b'!' | b'?' | b'/')) {
+                    rest.find('>').map_or(rest.len(), |end| end + 1)
+                } else {
+                    1
+                };
+                continue;
+            }
+
+            let (tag, len) = parse_tag(rest);
+            self.pos += len;
+            if RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) {
+                let rest = &self.html[self.pos..];
+                self.pos += find_end_tag(rest, &tag.name).unwrap_or(rest.len());
+            }
+            return Some(tag);
+        }
+    }
+}
+
+/// Parses a start tag at the beginning of the input, returning it along with its length.
+fn parse_tag(input: &str) -> (Tag<'_>, usize) {
+    let bytes = input.as_bytes();
+    let mut pos = 1;
+    let name_end = |from: usize| {
+        bytes[from..]
+            .iter()
+            .position(|b| b.is_ascii_whitespace() || matches!(b, b'>' | b'/' | b'='))
+            .map_or(bytes.len(), |len| from + len)
+    };
+
+    let end = name_end(pos);
+    let name = input[pos..end].to_ascii_lowercase();
+    pos = end;
+
+    let mut attributes = Vec::new();
+    loop {
+        while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'/') {
+            pos += 1;
+        }
+        if pos >= bytes.len() {
+            break;
+        }
+        if bytes[pos] == b'>' {
+            pos += 1;
+            break;
+        }
+
+        let end = name_end(pos + 1);
+        let attribute = input[pos..end].to_ascii_lowercase();
+        pos = end;
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if bytes.get(pos) != Some(&b'=') {
+            attributes.push((attribute, Cow::Borrowed("")));
+            continue;
+        }
+        pos += 1;
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+
+        let value = match bytes.get(pos) {
+            Some(quote @ (b'"' | b'\'')) => {
+                let len = bytes[pos + 1..]
+                    .iter()
+                    .position(|b| b == quote)
+                    .unwrap_or(bytes.len() - pos - 1);
+                let value = &input[pos + 1..pos + 1 + len];
+                pos = (pos + len + 2).min(bytes.len());
+                value
+            }
+            _ => {
+                let len = bytes[pos..]
+                    .iter()
+                    .position(|b| b.is_ascii_whitespace() || *b == b'>')
+                    .unwrap_or(bytes.len() - pos);
+                let value = &input[pos..pos + len];
+                pos += len;
+                value
+            }
+        };
+        attributes.push((attribute, decode_entities(value)));
+    }
+
+    (Tag { name, attributes }, pos)
+}
+
+/// Finds the end tag of an element, ignoring case.
+fn find_end_tag(input: &str, name: &str) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut pos = 0;
+    while let Some(start) = input[pos..].find("</") {
+        let start = pos + start;
+        let end = start + 2 + name.len();
+        if bytes.len() >= end && bytes[start + 2..end].eq_ignore_ascii_case(name.as_bytes()) {
+            return Some(start);
+        }
+        pos = start + 2;
+    }
+    None
+}
+
+/// Decodes the character references of an attribute value which matter for URLs and directives.
+pub(crate) fn decode_entities(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest[1..]
+            .find(';')
+            .filter(|len| *len <= 10)
+            .map(|len| &rest[1..1 + len]);
+        let ch = reference.and_then(|reference| match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let number = reference.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (ch, reference) {
+            (Some(ch), Some(reference)) => {
+                decoded.push(ch);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
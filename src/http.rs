@@ -0,0 +1,205 @@
+use std::{
+    io::Read,
+    time::{Duration, SystemTime},
+};
+
+#[cfg(feature = "http-async")]
+use async_trait::async_trait;
+use url::Url;
+
+#[cfg(feature = "http-async")]
+use crate::AsyncRobotsFetcher;
+use crate::{
+    AddRobots, CheckError, DEFAULT_RETRY_TTL, FetchOutcome, MetaBot, RobotsFetcher, robots_url,
+};
+
+/// The number of bytes of a `robots.txt` which are read at most. RFC 9309 requires parsing at least 500 kibibytes.
+pub const DEFAULT_MAX_SIZE: u64 = 500 * 1024;
+
+/// The time a request for a `robots.txt` may take at most.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of consecutive redirects followed at most, as recommended by RFC 9309.
+const MAX_REDIRECTS: u32 = 5;
+
+/// Fetches `robots.txt`s over HTTP(S).
+///
+/// Follows up to five redirects. Responses larger than the size cap are truncated.
+/// Status codes 4xx and exceeding the redirect limit result in [`FetchOutcome::Unavailable`],
+/// while status codes 5xx, timeouts and network errors result in [`FetchOutcome::Unreachable`].
+#[derive(Clone, Debug)]
+pub struct HttpFetcher {
+    agent: ureq::Agent,
+    max_size: u64,
+}
+
+/// A fetched `robots.txt` along with the caching headers of its response.
+struct Response {
+    outcome: FetchOutcome,
+    cache_control: Option<String>,
+    expires: Option<String>,
+}
+
+impl HttpFetcher {
+    /// Creates a new [`HttpFetcher`] identifying itself with `user_agent`, using the default size cap and timeout.
+    pub fn new(user_agent: &str) -> Self {
+        Self::with_limits(user_agent, DEFAULT_MAX_SIZE, DEFAULT_TIMEOUT)
+    }
+
+    /// Creates a new [`HttpFetcher`] identifying itself with `user_agent`.
+    /// `max_size` is the number of bytes read at most, `timeout` the time a request may take at most.
+    pub fn with_limits(user_agent: &str, max_size: u64, timeout: Duration) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .user_agent(user_agent)
+            .max_redirects(MAX_REDIRECTS)
+            .http_status_as_error(false)
+            .timeout_global(Some(timeout))
+            .build()
+            .new_agent();
+        Self { agent, max_size }
+    }
+
+    fn request(&self, robots_url: &Url) -> Response {
+        let unreachable = Response {
+            outcome: FetchOutcome::Unreachable,
+            cache_control: None,
+            expires: None,
+        };
+        let mut response = match self.agent.get(robots_url.as_str()).call() {
+            Ok(response) => response,
+            Err(ureq::Error::TooManyRedirects) => {
+                return Response {
+                    outcome: FetchOutcome::Unavailable,
+                    ..unreachable
+                };
+            }
+            Err(_) => return unreachable,
+        };
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let cache_control = header("cache-control");
+        let expires = header("expires");
+
+        let status = response.status();
+        let outcome = if status.is_success() {
+            let mut body = Vec::new();
+            let read = response
+                .body_mut()
+                .as_reader()
+                .take(self.max_size)
+                .read_to_end(&mut body);
+            match read {
+                Ok(_) => FetchOutcome::Found(String::from_utf8_lossy(&body).into_owned()),
+                Err(_) => FetchOutcome::Unreachable,
+            }
+        } else if status.is_client_error() || status.is_redirection() {
+            FetchOutcome::Unavailable
+        } else {
+            FetchOutcome::Unreachable
+        };
+
+        Response {
+            outcome,
+            cache_control,
+            expires,
+        }
+    }
+
+    /// Fetches the `robots.txt` responsible for `url` and adds it to `meta`.
+    /// The expiry of the host is derived from the caching headers of the response,
+    /// except for unreachable `robots.txt`s which expire after [`DEFAULT_RETRY_TTL`].
+    /// # Errors
+    /// Will return `Err` if the URL doesn't contain host.
+    pub fn load(&self, meta: &mut MetaBot, url: &Url) -> Result<(), CheckError> {
+        let robots = robots_url(url).ok_or(CheckError::MissingHost)?;
+        let fetched_at = SystemTime::now();
+        let response = self.request(&robots);
+        Self::store(meta, url, fetched_at, response)
+    }
+
+    /// Fetches the `robots.txt` responsible for `url` without blocking and adds it to `meta`.
+    /// The expiry of the host is derived from the caching headers of the response,
+    /// except for unreachable `robots.txt`s which expire after [`DEFAULT_RETRY_TTL`].
+    /// # Errors
+    /// Will return `Err` if the URL doesn't contain host.
+    #[cfg(feature = "http-async")]
+    pub async fn load_async(&self, meta: &mut MetaBot, url: &Url) -> Result<(), CheckError> {
+        let robots = robots_url(url).ok_or(CheckError::MissingHost)?;
+        let fetched_at = SystemTime::now();
+        let fetcher = self.clone();
+        let response = blocking::unblock(move || fetcher.request(&robots)).await;
+        Self::store(meta, url, fetched_at, response)
+    }
+
+    fn store(
+        meta: &mut MetaBot,
+        url: &Url,
+        fetched_at: SystemTime,
+        response: Response,
+    ) -> Result<(), CheckError> {
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        let unreachable = response.outcome == FetchOutcome::Unreachable;
+        // A fetched robots.txt replaces the previous one instead of extending it.
+        meta.remove(&host);
+        meta.add_robots(host.clone(), response.outcome);
+        if unreachable {
+            meta.set_expiry(&host, fetched_at, DEFAULT_RETRY_TTL);
+        } else {
+            meta.set_expiry_from_headers(
+                &host,
+                fetched_at,
+                response.cache_control.as_deref(),
+                response.expires.as_deref(),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sitemap")]
+impl HttpFetcher {
+    /// Fetches a sitemap, e.g. one listed by [`crate::SingleBot::sitemaps`], and reads it as it is downloaded.
+    /// The size cap doesn't apply to sitemaps.
+    /// # Errors
+    /// Will return `Err` if the request fails or the response status isn't successful.
+    pub fn fetch_sitemap(
+        &self,
+        url: &Url,
+    ) -> Result<crate::SitemapReader<'static>, crate::SitemapError> {
+        let response = self
+            .agent
+            .get(url.as_str())
+            .call()
+            .map_err(|err| crate::SitemapError::Io(std::io::Error::other(err)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(crate::SitemapError::Io(std::io::Error::other(format!(
+                "unexpected status {status}"
+            ))));
+        }
+        let reader = std::io::BufReader::new(response.into_body().into_reader());
+        crate::SitemapReader::new(reader)
+    }
+}
+
+impl RobotsFetcher for HttpFetcher {
+    fn fetch(&self, robots_url: &Url) -> FetchOutcome {
+        self.request(robots_url).outcome
+    }
+}
+
+#[cfg(feature = "http-async")]
+#[async_trait]
+impl AsyncRobotsFetcher for HttpFetcher {
+    async fn fetch_async(&self, robots_url: &Url) -> FetchOutcome {
+        let fetcher = self.clone();
+        let robots_url = robots_url.clone();
+        blocking::unblock(move || fetcher.request(&robots_url).outcome).await
+    }
+}
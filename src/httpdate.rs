@@ -0,0 +1,69 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parses an HTTP date (`Sun, 06 Nov 1994 08:49:37 GMT`, `Sunday, 06-Nov-94 08:49:37 GMT` or `Sun Nov  6 08:49:37 1994`).
+pub(crate) fn parse_http_date(input: &str) -> Option<SystemTime> {
+    let input = input.trim();
+    // The weekday carries no information, so it is skipped entirely.
+    let rest = match input.split_once(',') {
+        Some((_, rest)) => rest,
+        None => input.split_once(' ').map_or(input, |(_, rest)| rest),
+    };
+    let parts: Vec<&str> = rest
+        .split([' ', '-'])
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    let (day, month, year, time) = match parts.as_slice() {
+        // IMF-fixdate and RFC 850
+        [day, month, year, time, ..] if day.bytes().all(|b| b.is_ascii_digit()) => {
+            (*day, *month, *year, *time)
+        }
+        // asctime
+        [month, day, time, year] => (*day, *month, *year, *time),
+        _ => return None,
+    };
+
+    let day: u32 = day.parse().ok()?;
+    let month = MONTHS
+        .iter()
+        .position(|name| month.len() >= 3 && month[..3].eq_ignore_ascii_case(name))?
+        as u32
+        + 1;
+    let mut year: i64 = year.parse().ok()?;
+    if year < 100 {
+        year += if year < 70 { 2000 } else { 1900 };
+    }
+    let mut clock = time.splitn(3, ':').map(|part| part.parse::<u64>().ok());
+    let hours = clock.next()??;
+    let minutes = clock.next()??;
+    let seconds = clock.next()??;
+    if day == 0 || day > 31 || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    let days = u64::try_from(days_from_civil(year, month, day)?).ok()?;
+    let secs = days
+        .checked_mul(86_400)?
+        .checked_add(hours * 3_600 + minutes * 60 + seconds)?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Counts the days between the unix epoch and a date of the proleptic gregorian calendar.
+/// Returns `None` for years outside `1..=9999`, which no real date falls into.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    if !(1..=9999).contains(&year) {
+        return None;
+    }
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
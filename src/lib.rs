@@ -59,23 +59,106 @@ pub use trie::*;
 mod singlebot;
 pub use singlebot::*;
 
+mod recogniser;
+pub use recogniser::*;
+
+mod cleanparam;
+pub use cleanparam::*;
+
+mod profile;
+pub use profile::*;
+
+mod agentbot;
+pub use agentbot::*;
+
+mod useragent;
+pub use useragent::*;
+
+mod diagnostics;
+pub use diagnostics::*;
+
+mod robotstxt;
+pub use robotstxt::*;
+
+mod lint;
+pub use lint::*;
+
+mod diff;
+pub use diff::*;
+
+mod html;
+
+mod robotstag;
+pub use robotstag::*;
+
+mod registry;
+pub use registry::*;
+
+#[cfg(feature = "sitemap")]
+mod sitemap;
+#[cfg(feature = "sitemap")]
+pub use sitemap::*;
+
+mod httpdate;
+
 #[cfg(feature = "meta")]
 mod metabot;
 #[cfg(feature = "meta")]
 pub use metabot::*;
 
+#[cfg(feature = "meta")]
+mod boundedbot;
+#[cfg(feature = "meta")]
+pub use boundedbot::*;
+
+#[cfg(feature = "meta")]
+mod concurrentbot;
+#[cfg(feature = "meta")]
+pub use concurrentbot::*;
+
+#[cfg(feature = "meta")]
+mod fetch;
+#[cfg(feature = "meta")]
+pub use fetch::*;
+
+#[cfg(feature = "meta")]
+mod scheduler;
+#[cfg(feature = "meta")]
+pub use scheduler::*;
+
+#[cfg(all(feature = "meta", feature = "sitemap"))]
+mod audit;
+#[cfg(all(feature = "meta", feature = "sitemap"))]
+pub use audit::*;
+
+#[cfg(feature = "meta")]
+mod report;
+#[cfg(feature = "meta")]
+pub use report::*;
+
+#[cfg(feature = "links")]
+mod links;
+#[cfg(feature = "links")]
+pub use links::*;
+
+#[cfg(feature = "tdm")]
+mod tdm;
+#[cfg(feature = "tdm")]
+pub use tdm::*;
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::*;
+
 /// The permission given for a URL.
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
 pub enum Permission {
     /// Accessing the URL is allowed.
     Allowed,
     /// Accessing the URL is disallowed.
     Denied,
     /// No permission has been specified for this URL, so accessing it is allowed.
+    #[default]
     Unspecified,
 }
-impl Default for Permission {
-    fn default() -> Self {
-        Self::Unspecified
-    }
-}
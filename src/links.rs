@@ -0,0 +1,132 @@
+use url::Url;
+
+use crate::{CheckURL, MetaBot, PageRobots, Permission, html::tags};
+
+/// Extracts the links of HTML documents, keeping apart those a crawler may not follow.
+///
+/// Links are taken from the `href` of `<a>` and `<area>` elements and resolved against the `<base>` of the document, if any.
+/// Only `http` and `https` links are kept, without fragment.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LinkExtractor {
+    skipped_rels: Vec<String>,
+    respect_meta_nofollow: bool,
+}
+
+/// The links of an HTML document, in order of appearance and without duplicates.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ExtractedLinks {
+    /// Links which may be crawled, either because a rule allows it or because no rule matches.
+    pub allowed: Vec<Url>,
+    /// Links disallowed by the `robots.txt` of their host.
+    pub denied: Vec<Url>,
+    /// Links which must not be followed because of their `rel` or a page-level `nofollow`.
+    pub nofollow: Vec<Url>,
+    /// Links which couldn't be resolved to a URL, as written.
+    pub invalid: Vec<String>,
+}
+
+impl ExtractedLinks {
+    fn contains(&self, url: &Url) -> bool {
+        self.allowed.contains(url) || self.denied.contains(url) || self.nofollow.contains(url)
+    }
+}
+
+impl Default for LinkExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkExtractor {
+    /// Creates a new [`LinkExtractor`] which respects page-level `nofollow`, but follows links regardless of their `rel`.
+    pub fn new() -> Self {
+        Self {
+            skipped_rels: Vec::new(),
+            respect_meta_nofollow: true,
+        }
+    }
+
+    /// Sets the `rel` values of links which aren't followed, e.g. `["nofollow", "ugc", "sponsored"]`.
+    pub fn set_skipped_rels(&mut self, rels: &[&str]) {
+        self.skipped_rels = rels.iter().map(|rel| rel.to_ascii_lowercase()).collect();
+    }
+
+    /// Sets whether a `nofollow` in the robots meta tags of a document applies to all of its links. Defaults to `true`.
+    pub fn set_respect_meta_nofollow(&mut self, respect: bool) {
+        self.respect_meta_nofollow = respect;
+    }
+
+    /// Extracts the links of a document located at `base`, checking them against `meta`.
+    /// The robots meta tags of the document are read for the user agent of `meta`.
+    pub fn extract(&self, meta: &MetaBot, base: &Url, html: &str) -> ExtractedLinks {
+        self.extract_with_headers(meta, base, html, &[])
+    }
+
+    /// Like [`LinkExtractor::extract`], but also respects the `X-Robots-Tag` headers the document was served with.
+    pub fn extract_with_headers(
+        &self,
+        meta: &MetaBot,
+        base: &Url,
+        html: &str,
+        x_robots_tags: &[&str],
+    ) -> ExtractedLinks {
+        let mut page = PageRobots::new(meta.user_agent().map(str::to_owned));
+        for value in x_robots_tags {
+            page.add_header(value);
+        }
+        page.add_html(html);
+        let page_nofollow = self.respect_meta_nofollow && page.directives().nofollow;
+
+        let mut base = base.clone();
+        let mut links = ExtractedLinks::default();
+        let mut seen_base = false;
+        for tag in tags(html) {
+            if tag.name == "base" && !seen_base {
+                seen_base = true;
+                if let Some(href) = tag.attribute("href")
+                    && let Ok(url) = base.join(href.trim())
+                {
+                    base = url;
+                }
+                continue;
+            }
+            if tag.name != "a" && tag.name != "area" {
+                continue;
+            }
+            let Some(href) = tag.attribute("href") else {
+                continue;
+            };
+            let href = href.trim();
+            let mut url = match base.join(href) {
+                Ok(url) => url,
+                Err(_) => {
+                    if !links.invalid.iter().any(|invalid| invalid == href) {
+                        links.invalid.push(href.to_owned());
+                    }
+                    continue;
+                }
+            };
+            if url.scheme() != "http" && url.scheme() != "https" {
+                continue;
+            }
+            url.set_fragment(None);
+            if links.contains(&url) {
+                continue;
+            }
+
+            let skipped = page_nofollow
+                || tag.attribute("rel").is_some_and(|rel| {
+                    rel.split_ascii_whitespace()
+                        .any(|rel| self.skipped_rels.contains(&rel.to_ascii_lowercase()))
+                });
+            if skipped {
+                links.nofollow.push(url);
+            } else if meta.check(url.clone()) == Ok(Permission::Denied) {
+                links.denied.push(url);
+            } else {
+                links.allowed.push(url);
+            }
+        }
+        links
+    }
+}
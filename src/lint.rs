@@ -0,0 +1,246 @@
+use std::fmt::Display;
+
+use crate::{
+    Group, ParseProfile, Permission, PrefixTrie, RobotsTxt, Rule, singlebot::decode_pattern,
+    trie::witnesses,
+};
+
+/// Names of directories which typically hold CSS and JavaScript.
+const ASSET_DIRECTORIES: [&str; 9] = [
+    "assets",
+    "static",
+    "css",
+    "js",
+    "scripts",
+    "styles",
+    "dist",
+    "build",
+    "wp-includes",
+];
+
+/// The kind of issue a [`Finding`] reports.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum LintKind {
+    /// The rule never decides a path, because other rules of its group are at least as specific wherever it matches.
+    ShadowedRule,
+    /// The rule denies CSS or JavaScript files, which search engines need to render pages.
+    BlocksAssets,
+    /// The `*` group denies everything, while a group for some other user agent doesn't.
+    StarDisallowsAll,
+    /// A user agent is named by more than one group.
+    DuplicateGroup,
+    /// The pattern contains text after a `$`, which only anchors a pattern at its end.
+    TextAfterDollar,
+}
+
+/// A semantic issue found in a `robots.txt`.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Finding {
+    /// The kind of issue.
+    pub kind: LintKind,
+    /// The number of the line the issue is found on, starting at 1.
+    pub line: usize,
+    /// The number of another line involved in the issue, e.g. the line of the rule shadowing a rule.
+    pub related_line: Option<usize>,
+    /// A description of the issue.
+    pub message: String,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The rules of a group in a trie, mapping each pattern to the index of the rule deciding it.
+struct GroupTrie<'a> {
+    rules: Vec<&'a Rule>,
+    keys: Vec<String>,
+    trie: PrefixTrie<usize>,
+}
+
+impl<'a> GroupTrie<'a> {
+    /// Rules with the same pattern are settled like the bots of `profile` do.
+    fn new(groups: &[&'a Group], profile: ParseProfile) -> Self {
+        let rules: Vec<_> = groups.iter().flat_map(|group| &group.rules).collect();
+        let keys: Vec<_> = rules
+            .iter()
+            .map(|rule| decode_pattern(rule.pattern.clone()))
+            .collect();
+        let mut trie = PrefixTrie::new();
+        for (idx, key) in keys.iter().enumerate() {
+            if let Some(prev) = trie.insert(key, idx)
+                && profile.prefers_allow()
+                && rules[prev].permission == Permission::Allowed
+                && rules[idx].permission == Permission::Denied
+            {
+                trie.insert(key, prev);
+            }
+        }
+        Self { rules, keys, trie }
+    }
+
+    /// Returns the rule deciding a path, if any.
+    fn decide(&self, path: &str) -> Option<usize> {
+        self.trie.get(path)
+    }
+
+    /// Returns paths matched by the rule.
+    fn witnesses(&self, idx: usize) -> Vec<String> {
+        witnesses(&self.keys[idx])
+    }
+
+    fn is_exact(&self, idx: usize) -> bool {
+        self.keys[idx].ends_with('\0')
+    }
+}
+
+/// Analyses a parsed `robots.txt` for semantic issues, returning the findings ordered by line.
+///
+/// Overlap between rules is judged by looking up example paths in a [`PrefixTrie`], so findings are a close approximation.
+pub fn lint(robots: &RobotsTxt) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for group in &robots.groups {
+        let trie = GroupTrie::new(&[group], robots.profile);
+        shadowed_rules(&trie, &mut findings);
+        blocked_assets(&trie, &mut findings);
+        for rule in &group.rules {
+            if rule.pattern.trim_end_matches('$').contains('$') {
+                findings.push(Finding {
+                    kind: LintKind::TextAfterDollar,
+                    line: rule.line,
+                    related_line: None,
+                    message: format!(
+                        "`{rule}` has text after `$`, which only anchors at the end of a pattern and is matched literally elsewhere"
+                    ),
+                });
+            }
+        }
+    }
+    star_disallows_all(robots, &mut findings);
+    duplicate_groups(robots, &mut findings);
+
+    findings.sort_by_key(|finding| finding.line);
+    findings
+}
+
+fn shadowed_rules(trie: &GroupTrie, findings: &mut Vec<Finding>) {
+    for (idx, rule) in trie.rules.iter().enumerate() {
+        let decisions: Vec<_> = trie
+            .witnesses(idx)
+            .iter()
+            .map(|path| trie.decide(path))
+            .collect();
+        if decisions.contains(&Some(idx)) {
+            continue;
+        }
+        let Some(Some(other)) = decisions.into_iter().find(Option::is_some) else {
+            continue;
+        };
+        let other_rule = trie.rules[other];
+        let message = if trie.keys[other] == trie.keys[idx] {
+            format!(
+                "`{rule}` is overridden by `{other_rule}` on line {}",
+                other_rule.line
+            )
+        } else {
+            format!(
+                "`{rule}` never applies, because `{other_rule}` on line {} takes precedence wherever it matches",
+                other_rule.line
+            )
+        };
+        findings.push(Finding {
+            kind: LintKind::ShadowedRule,
+            line: rule.line,
+            related_line: Some(other_rule.line),
+            message,
+        });
+    }
+}
+
+fn blocked_assets(trie: &GroupTrie, findings: &mut Vec<Finding>) {
+    for (idx, rule) in trie.rules.iter().enumerate() {
+        if rule.permission != Permission::Denied {
+            continue;
+        }
+        let witness = trie.witnesses(idx).swap_remove(0);
+        let directory = witness
+            .strip_suffix('/')
+            .and_then(|dir| dir.rsplit('/').next())
+            .is_some_and(|dir| ASSET_DIRECTORIES.contains(&dir.to_lowercase().as_str()));
+        let probes = if directory && !trie.is_exact(idx) {
+            vec![format!("{witness}style.css"), format!("{witness}script.js")]
+        } else {
+            vec![witness]
+        };
+        let blocked = probes.into_iter().find(|probe| {
+            (probe.ends_with(".css") || probe.ends_with(".js")) && trie.decide(probe) == Some(idx)
+        });
+        if let Some(probe) = blocked {
+            findings.push(Finding {
+                kind: LintKind::BlocksAssets,
+                line: rule.line,
+                related_line: None,
+                message: format!(
+                    "`{rule}` blocks CSS or JavaScript such as `{probe}`, which search engines need to render pages"
+                ),
+            });
+        }
+    }
+}
+
+fn denies_everything<'a>(trie: &GroupTrie<'a>) -> Option<&'a Rule> {
+    let idx = trie.decide("/")?;
+    let rule = trie.rules[idx];
+    (rule.permission == Permission::Denied && trie.keys[idx] == "/").then_some(rule)
+}
+
+fn star_disallows_all(robots: &RobotsTxt, findings: &mut Vec<Finding>) {
+    let star: Vec<_> = robots
+        .groups
+        .iter()
+        .filter(|group| group.is_star())
+        .collect();
+    let Some(rule) = denies_everything(&GroupTrie::new(&star, robots.profile)) else {
+        return;
+    };
+    for group in robots.groups.iter().filter(|group| !group.is_star()) {
+        if denies_everything(&GroupTrie::new(&[group], robots.profile)).is_none() {
+            findings.push(Finding {
+                kind: LintKind::StarDisallowsAll,
+                line: rule.line,
+                related_line: Some(group.line),
+                message: format!(
+                    "`{rule}` for `*` denies every user agent, yet `{}` on line {} may still crawl",
+                    group.user_agents.join("`, `"),
+                    group.line
+                ),
+            });
+        }
+    }
+}
+
+fn duplicate_groups(robots: &RobotsTxt, findings: &mut Vec<Finding>) {
+    for (idx, group) in robots.groups.iter().enumerate() {
+        for agent in &group.user_agents {
+            let earlier = robots.groups[..idx].iter().find(|other| {
+                other
+                    .user_agents
+                    .iter()
+                    .any(|other_agent| other_agent.eq_ignore_ascii_case(agent))
+            });
+            if let Some(earlier) = earlier {
+                findings.push(Finding {
+                    kind: LintKind::DuplicateGroup,
+                    line: group.line,
+                    related_line: Some(earlier.line),
+                    message: format!(
+                        "`{agent}` already has a group on line {}, parsers may use either or merge them",
+                        earlier.line
+                    ),
+                });
+            }
+        }
+    }
+}
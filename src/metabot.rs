@@ -1,14 +1,234 @@
-use std::{collections::HashMap, ops::AddAssign};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::AddAssign,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
-use url::{Host, ParseError, Url};
+use url::{Host, ParseError, Position, Url};
 
-use crate::{Permission, SingleBot};
+use crate::{
+    AgentBot, AgentView, ContentSignals, ParseProfile, Permission, Recogniser, Verdict,
+    httpdate::parse_http_date,
+};
+
+/// The longest time a `robots.txt` should be cached for, as recommended by RFC 9309.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// Used to check the `robots.txt`s of multiple Hosts.
 pub struct MetaBot {
-    hosts: HashMap<Host<String>, SingleBot>,
+    hosts: HashMap<Host<String>, HostEntry>,
     user_agent: Option<String>,
+    profile: ParseProfile,
+    recogniser: Recogniser,
+    ttl: Duration,
+    expired: ExpiredFallback,
+    interner: Interner,
+    /// The approximate number of bytes occupied by the host entries, without their bots.
+    host_bytes: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct HostEntry {
+    bot: Arc<AgentBot>,
+    /// The segments of the bot applying to the user agent of the [`MetaBot`].
+    selected: Box<[bool]>,
+    fetched_at: SystemTime,
+    ttl: Duration,
+}
+
+/// Keeps one shared instance of every distinct bot, so hosts with identical `robots.txt`s share their bot.
+///
+/// Users are counted per interner rather than with [`Arc::strong_count`], as clones of a [`MetaBot`] share the instances.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Interner {
+    bots: HashMap<u64, Vec<Interned>>,
+    /// The approximate number of bytes occupied by the distinct bots, each counted once.
+    bytes: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Interned {
+    bot: Arc<AgentBot>,
+    /// The number of hosts using the bot.
+    users: usize,
+    /// The approximate number of bytes occupied by the bot.
+    size: usize,
+}
+
+impl Interner {
+    fn hash(bot: &AgentBot) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        bot.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the shared instance of a bot, adding it if no equal bot is known.
+    fn intern(&mut self, bot: AgentBot) -> Arc<AgentBot> {
+        let bucket = self.bots.entry(Self::hash(&bot)).or_default();
+        if let Some(shared) = bucket.iter_mut().find(|shared| *shared.bot == bot) {
+            shared.users += 1;
+            return shared.bot.clone();
+        }
+        let shared = Arc::new(bot);
+        let size = shared.approximate_size();
+        bucket.push(Interned {
+            bot: shared.clone(),
+            users: 1,
+            size,
+        });
+        self.bytes += size;
+        shared
+    }
+
+    /// Adds a user to an already shared instance, adding the instance if it is not known.
+    fn adopt(&mut self, bot: &Arc<AgentBot>) {
+        let bucket = self.bots.entry(Self::hash(bot)).or_default();
+        match bucket
+            .iter_mut()
+            .find(|shared| Arc::ptr_eq(&shared.bot, bot))
+        {
+            Some(shared) => shared.users += 1,
+            None => {
+                let size = bot.approximate_size();
+                bucket.push(Interned {
+                    bot: bot.clone(),
+                    users: 1,
+                    size,
+                });
+                self.bytes += size;
+            }
+        }
+    }
+
+    /// Gives up a reference to a shared bot, forgetting the bot once no host uses it anymore.
+    fn release(&mut self, bot: Arc<AgentBot>) {
+        self.forget_user(&bot);
+    }
+
+    /// Gives up a reference to a shared bot and returns it for modification, copying it if it is still used elsewhere.
+    fn take(&mut self, bot: Arc<AgentBot>) -> AgentBot {
+        self.forget_user(&bot);
+        Arc::unwrap_or_clone(bot)
+    }
+
+    fn forget_user(&mut self, bot: &Arc<AgentBot>) {
+        let hash = Self::hash(bot);
+        let Some(bucket) = self.bots.get_mut(&hash) else {
+            return;
+        };
+        if let Some(idx) = bucket
+            .iter()
+            .position(|shared| Arc::ptr_eq(&shared.bot, bot))
+        {
+            bucket[idx].users -= 1;
+            if bucket[idx].users == 0 {
+                self.bytes -= bucket.swap_remove(idx).size;
+            }
+        }
+        if bucket.is_empty() {
+            self.bots.remove(&hash);
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Interned> {
+        self.bots.values().flatten()
+    }
+}
+
+/// How many hosts of a [`MetaBot`] share their bot with other hosts.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SharingStats {
+    /// The number of hosts with a known `robots.txt`.
+    pub hosts: usize,
+    /// The number of distinct bots stored for these hosts.
+    pub distinct_bots: usize,
+    /// The number of hosts whose bot is shared with at least one other host.
+    pub shared_hosts: usize,
+    /// The estimated number of bytes saved by sharing bots.
+    pub saved_bytes: usize,
+}
+
+impl SharingStats {
+    /// Returns the average number of hosts sharing a bot. `1.0` means no bot is shared.
+    pub fn sharing_ratio(&self) -> f64 {
+        if self.distinct_bots == 0 {
+            return 1.0;
+        }
+        self.hosts as f64 / self.distinct_bots as f64
+    }
+}
+
+/// Checks whether something fetched at `fetched_at` and valid for `ttl` has expired by `now`.
+pub(crate) fn is_expired(fetched_at: SystemTime, ttl: Duration, now: SystemTime) -> bool {
+    match now.duration_since(fetched_at) {
+        Ok(age) => age >= ttl,
+        Err(_) => false,
+    }
+}
+
+impl HostEntry {
+    fn is_stale(&self, now: SystemTime) -> bool {
+        is_expired(self.fetched_at, self.ttl, now)
+    }
+
+    fn view(&self) -> AgentView<'_> {
+        AgentView::new(&self.bot, Cow::Borrowed(&self.selected))
+    }
+
+    /// Estimates the number of bytes occupied by the entry of a host, without its shared bot.
+    fn size(&self, host: &Host<String>) -> usize {
+        size_of::<(Host<String>, HostEntry)>() + host_name_len(host) + self.selected.len()
+    }
+}
+
+/// How [`MetaBot`] answers checks for hosts whose `robots.txt` has expired.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub enum ExpiredFallback {
+    /// Keep using the expired `robots.txt` until it is replaced.
+    #[default]
+    Stale,
+    /// Treat the host as if no `robots.txt` was known.
+    Unspecified,
+    /// Deny every URL on the host until a fresh `robots.txt` is added.
+    Denied,
+}
+
+/// A URL without the query parameters listed by `Clean-param` directives, see [`MetaBot::canonicalise`].
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct CanonicalUrl {
+    /// The URL without the listed parameters.
+    pub url: Url,
+    /// The same URL on the preferred mirror declared by `Host`, if that is another origin.
+    pub mirror: Option<Url>,
+}
+
+impl CanonicalUrl {
+    /// Returns the URL on the preferred mirror if there is one, so equivalent URLs of every mirror collapse to it.
+    pub fn preferred(&self) -> &Url {
+        self.mirror.as_ref().unwrap_or(&self.url)
+    }
+}
+
+/// Moves a URL to the mirror declared by a `Host` directive like `example.com` or `https://example.com:8080`.
+/// Mirrors without scheme use `http`. Returns `None` if the URL is already on the mirror or the mirror is invalid.
+fn mirror_of(url: &Url, declared: &str) -> Option<Url> {
+    let origin = if declared.contains("://") {
+        Url::parse(declared)
+    } else {
+        Url::parse(&format!("http://{declared}"))
+    }
+    .ok()?;
+    let mirror = Url::parse(&format!(
+        "{}{}",
+        &origin[..Position::BeforePath],
+        &url[Position::BeforePath..]
+    ))
+    .ok()?;
+    (mirror.origin() != url.origin()).then_some(mirror)
 }
 
 /// Allows for adding `robots.txt`s.
@@ -19,14 +239,29 @@ pub trait AddRobots<T> {
 
 impl<T> AddRobots<T> for MetaBot
 where
-    SingleBot: AddAssign<T>,
+    AgentBot: AddAssign<T>,
 {
     fn add_robots(&mut self, host: Host<String>, robots_txt: T) {
-        let bot = self
-            .hosts
-            .entry(host)
-            .or_insert_with(|| SingleBot::new(self.user_agent.clone()));
+        let mut bot = match self.hosts.remove(&host) {
+            Some(entry) => {
+                self.host_bytes -= entry.size(&host);
+                self.interner.take(entry.bot)
+            }
+            None => {
+                let mut bot = AgentBot::with_profile(self.profile);
+                bot.set_recogniser(self.recogniser);
+                bot
+            }
+        };
         bot.add_assign(robots_txt);
+        let entry = HostEntry {
+            selected: bot.selection(self.user_agent.as_deref()).into(),
+            bot: self.interner.intern(bot),
+            fetched_at: SystemTime::now(),
+            ttl: self.ttl,
+        };
+        self.host_bytes += entry.size(&host);
+        self.hosts.insert(host, entry);
     }
 }
 
@@ -59,6 +294,23 @@ pub trait CheckURL<T> {
     fn check(&self, url: T) -> Result<Permission, CheckError>;
 }
 
+/// Allows for checking the permissions for a URL at a given point in time.
+pub trait CheckURLAt<T> {
+    /// Checks the permissions for a URL, treating `robots.txt`s that have expired by `now` according to the configured [`ExpiredFallback`].
+    /// # Errors
+    /// Will return `Err` if URL parsing fails or URL doesn't contain host.
+    fn check_at(&self, url: T, now: SystemTime) -> Result<Permission, CheckError>;
+}
+
+impl<T> CheckURL<T> for MetaBot
+where
+    MetaBot: CheckURLAt<T>,
+{
+    fn check(&self, url: T) -> Result<Permission, CheckError> {
+        self.check_at(url, SystemTime::now())
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 /// Error if checking fails because of an invalid URL.
 pub enum CheckError {
@@ -68,13 +320,14 @@ pub enum CheckError {
     MissingHost,
 }
 
-impl CheckURL<Url> for MetaBot {
-    fn check(&self, url: Url) -> Result<Permission, CheckError> {
+impl CheckURLAt<Url> for MetaBot {
+    fn check_at(&self, url: Url, now: SystemTime) -> Result<Permission, CheckError> {
         if let Some(host) = url.host() {
-            if let Some(bot) = self.hosts.get(&host.to_owned()) {
-                Ok(bot.check(url.path()))
-            } else {
-                Ok(Permission::Unspecified)
+            match self.hosts.get(&host.to_owned()) {
+                Some(entry) => Ok(self
+                    .expired_permission(entry, now)
+                    .unwrap_or_else(|| entry.view().check(url.path()))),
+                None => Ok(Permission::Unspecified),
             }
         } else {
             Err(CheckError::MissingHost)
@@ -82,24 +335,24 @@ impl CheckURL<Url> for MetaBot {
     }
 }
 
-impl CheckURL<&str> for MetaBot {
-    fn check(&self, url: &str) -> Result<Permission, CheckError> {
+impl CheckURLAt<&str> for MetaBot {
+    fn check_at(&self, url: &str, now: SystemTime) -> Result<Permission, CheckError> {
         match Url::parse(url) {
-            Ok(parsed) => self.check(parsed),
+            Ok(parsed) => self.check_at(parsed, now),
             Err(err) => Err(CheckError::ParseError(err)),
         }
     }
 }
 
-impl CheckURL<&String> for MetaBot {
-    fn check(&self, url: &String) -> Result<Permission, CheckError> {
-        self.check(url.as_str())
+impl CheckURLAt<&String> for MetaBot {
+    fn check_at(&self, url: &String, now: SystemTime) -> Result<Permission, CheckError> {
+        self.check_at(url.as_str(), now)
     }
 }
 
-impl CheckURL<String> for MetaBot {
-    fn check(&self, url: String) -> Result<Permission, CheckError> {
-        self.check(url.as_str())
+impl CheckURLAt<String> for MetaBot {
+    fn check_at(&self, url: String, now: SystemTime) -> Result<Permission, CheckError> {
+        self.check_at(url.as_str(), now)
     }
 }
 
@@ -107,14 +360,309 @@ impl MetaBot {
     /// Creates a new [`MetaBot`].
     /// [`MetaBot`] is used to check multiple Hosts. If checking only on a single host, use [`crate::SingleBot`]
     pub fn new(user_agent: Option<String>) -> Self {
+        Self::with_profile(user_agent, ParseProfile::default())
+    }
+
+    /// Creates a new [`MetaBot`] which interprets `robots.txt`s like the crawlers of a [`ParseProfile`].
+    pub fn with_profile(user_agent: Option<String>, profile: ParseProfile) -> Self {
         let hosts = HashMap::new();
-        Self { hosts, user_agent }
+        Self {
+            hosts,
+            user_agent,
+            profile,
+            recogniser: profile.recogniser(),
+            ttl: DEFAULT_TTL,
+            expired: ExpiredFallback::default(),
+            interner: Interner::default(),
+            host_bytes: 0,
+        }
     }
 
     /// Shrinks the internal data structure, saving a few bytes.
     pub fn shrink(&mut self) {
-        for bot in self.hosts.values_mut() {
-            bot.shrink();
+        self.hosts.shrink_to_fit();
+        // Shared bots are replaced by shrunk copies, which are shared the same way.
+        let mut shrunk: HashMap<*const AgentBot, Arc<AgentBot>> = HashMap::new();
+        for entry in self.hosts.values_mut() {
+            let copy = shrunk
+                .entry(Arc::as_ptr(&entry.bot))
+                .or_insert_with(|| {
+                    let mut bot = (*entry.bot).clone();
+                    bot.shrink();
+                    Arc::new(bot)
+                })
+                .clone();
+            entry.bot = copy;
+        }
+        self.interner = Interner::default();
+        for entry in self.hosts.values() {
+            self.interner.adopt(&entry.bot);
         }
     }
+
+    /// Reports how many hosts share their bot because their `robots.txt`s are identical.
+    pub fn sharing_stats(&self) -> SharingStats {
+        let mut stats = SharingStats {
+            hosts: self.hosts.len(),
+            ..Default::default()
+        };
+        for Interned { users, size, .. } in self.interner.iter() {
+            stats.distinct_bots += 1;
+            if *users > 1 {
+                stats.shared_hosts += users;
+                stats.saved_bytes += (users - 1) * size;
+            }
+        }
+        stats
+    }
+
+    /// Returns the profile the bot interprets `robots.txt`s with.
+    pub fn profile(&self) -> ParseProfile {
+        self.profile
+    }
+
+    /// Returns the user agent the bot checks for.
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+
+    /// Returns the number of hosts with a known `robots.txt`.
+    pub fn len(&self) -> usize {
+        self.hosts.len()
+    }
+
+    /// Returns `true` if no `robots.txt` is known for any host.
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+    }
+
+    /// Gets the bot of a host, bound to the configured user agent.
+    pub fn get(&self, host: &Host<String>) -> Option<AgentView<'_>> {
+        self.hosts.get(host).map(HostEntry::view)
+    }
+
+    /// Gets the bot of a host, bound to another user agent than the configured one.
+    pub fn get_as(&self, host: &Host<String>, user_agent: &str) -> Option<AgentView<'_>> {
+        self.hosts
+            .get(host)
+            .map(|entry| entry.bot.for_agent(Some(user_agent)))
+    }
+
+    /// Iterates over the hosts with a known `robots.txt`, in no particular order.
+    pub fn hosts(&self) -> impl Iterator<Item = &Host<String>> {
+        self.hosts.keys()
+    }
+
+    /// Returns the permission for a host whose `robots.txt` has expired by `now`, according to the configured [`ExpiredFallback`].
+    /// Returns `None` if the `robots.txt` is to be used.
+    fn expired_permission(&self, entry: &HostEntry, now: SystemTime) -> Option<Permission> {
+        match self.expired {
+            ExpiredFallback::Stale => None,
+            _ if !entry.is_stale(now) => None,
+            ExpiredFallback::Denied => Some(Permission::Denied),
+            ExpiredFallback::Unspecified => Some(Permission::Unspecified),
+        }
+    }
+
+    /// Checks the permission for a URL and tells which rule decided it.
+    /// Expired `robots.txt`s are treated according to the configured [`ExpiredFallback`], without a deciding rule.
+    /// # Errors
+    /// Will return `Err` if URL doesn't contain host.
+    pub fn explain(&self, url: &Url) -> Result<Verdict, CheckError> {
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        let verdict = match self.hosts.get(&host) {
+            Some(entry) => match self.expired_permission(entry, SystemTime::now()) {
+                Some(permission) => Verdict {
+                    permission,
+                    rule: None,
+                },
+                None => entry.view().explain(url.path()),
+            },
+            None => Verdict::default(),
+        };
+        Ok(verdict)
+    }
+
+    /// Checks the permission for a URL as another user agent than the configured one, without reparsing the `robots.txt`.
+    /// Expired `robots.txt`s are treated according to the configured [`ExpiredFallback`].
+    /// # Errors
+    /// Will return `Err` if URL doesn't contain host.
+    pub fn check_as(&self, user_agent: &str, url: &Url) -> Result<Permission, CheckError> {
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        let permission = match self.hosts.get(&host) {
+            Some(entry) => self
+                .expired_permission(entry, SystemTime::now())
+                .unwrap_or_else(|| entry.bot.check(user_agent, url.path())),
+            None => Permission::Unspecified,
+        };
+        Ok(permission)
+    }
+
+    /// Canonicalises a URL with the `robots.txt` of its host, removing the query parameters listed by `Clean-param`
+    /// directives and moving it to the preferred mirror declared by `Host`. Expired `robots.txt`s are used as well.
+    /// # Errors
+    /// Will return `Err` if URL doesn't contain host.
+    pub fn canonicalise(&self, url: &Url) -> Result<CanonicalUrl, CheckError> {
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        let Some(entry) = self.hosts.get(&host) else {
+            return Ok(CanonicalUrl {
+                url: url.clone(),
+                mirror: None,
+            });
+        };
+        let cleaned = entry.bot.clean_path(&url[Position::BeforePath..]);
+        let cleaned = Url::parse(&format!("{}{cleaned}", &url[..Position::BeforePath]))
+            .unwrap_or_else(|_| url.clone());
+        let mirror = entry
+            .bot
+            .host()
+            .and_then(|declared| mirror_of(&cleaned, declared));
+        Ok(CanonicalUrl {
+            url: cleaned,
+            mirror,
+        })
+    }
+
+    /// Returns how content of a host may be used by the configured user agent, as specified by `Content-Signal` directives.
+    /// Returns `None` if no `robots.txt` is known for the host or it expresses no preference.
+    pub fn content_signals(&self, host: &Host<String>) -> Option<ContentSignals> {
+        self.get(host)?.content_signals()
+    }
+
+    /// Checks whether a `robots.txt` is known for the host.
+    pub fn contains_host(&self, host: &Host<String>) -> bool {
+        self.hosts.contains_key(host)
+    }
+
+    /// Forgets the `robots.txt` of a host.
+    /// Returns `false` if no `robots.txt` was known for the host.
+    pub fn remove(&mut self, host: &Host<String>) -> bool {
+        let Some(entry) = self.hosts.remove(host) else {
+            return false;
+        };
+        self.host_bytes -= entry.size(host);
+        self.interner.release(entry.bot);
+        true
+    }
+
+    /// Estimates the number of bytes occupied by the known `robots.txt`s.
+    /// Bots shared by several hosts are counted once.
+    pub fn approximate_size(&self) -> usize {
+        self.host_bytes + self.interner.bytes
+    }
+
+    /// Sets the lifetime of newly added `robots.txt`s. It is also the upper bound for lifetimes derived from HTTP headers.
+    /// Defaults to [`DEFAULT_TTL`].
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    /// Sets how the directives of newly added `robots.txt`s are recognised.
+    /// Defaults to [`ParseProfile::recogniser`].
+    pub fn set_recogniser(&mut self, recogniser: Recogniser) {
+        self.recogniser = recogniser;
+    }
+
+    /// Sets how expired `robots.txt`s are treated when checking URLs.
+    pub fn set_expired_fallback(&mut self, fallback: ExpiredFallback) {
+        self.expired = fallback;
+    }
+
+    /// Sets when the `robots.txt` of a host was fetched and how long it stays valid.
+    /// Returns `false` if no `robots.txt` is known for the host.
+    pub fn set_expiry(
+        &mut self,
+        host: &Host<String>,
+        fetched_at: SystemTime,
+        ttl: Duration,
+    ) -> bool {
+        let Some(entry) = self.hosts.get_mut(host) else {
+            return false;
+        };
+        entry.fetched_at = fetched_at;
+        entry.ttl = ttl.min(self.ttl);
+        true
+    }
+
+    /// Sets the expiry of a host from the `Cache-Control` and `Expires` headers of the response its `robots.txt` was fetched with.
+    /// Falls back to the configured TTL if neither header specifies a lifetime.
+    /// Returns `false` if no `robots.txt` is known for the host.
+    pub fn set_expiry_from_headers(
+        &mut self,
+        host: &Host<String>,
+        fetched_at: SystemTime,
+        cache_control: Option<&str>,
+        expires: Option<&str>,
+    ) -> bool {
+        let ttl = ttl_from_headers(cache_control, expires, fetched_at).unwrap_or(self.ttl);
+        self.set_expiry(host, fetched_at, ttl)
+    }
+
+    /// Returns when the `robots.txt` of a host expires.
+    pub fn expires_at(&self, host: &Host<String>) -> Option<SystemTime> {
+        self.hosts
+            .get(host)
+            .map(|entry| entry.fetched_at + entry.ttl)
+    }
+
+    /// Checks whether the `robots.txt` of a host has expired by `now`.
+    /// Returns `None` if no `robots.txt` is known for the host.
+    pub fn is_stale(&self, host: &Host<String>, now: SystemTime) -> Option<bool> {
+        self.hosts.get(host).map(|entry| entry.is_stale(now))
+    }
+
+    /// Lists all hosts whose `robots.txt` has expired by `now` and should be fetched again.
+    pub fn stale_hosts(&self, now: SystemTime) -> impl Iterator<Item = &Host<String>> {
+        self.hosts
+            .iter()
+            .filter(move |(_, entry)| entry.is_stale(now))
+            .map(|(host, _)| host)
+    }
+}
+
+fn host_name_len(host: &Host<String>) -> usize {
+    match host {
+        Host::Domain(domain) => domain.capacity(),
+        _ => 0,
+    }
+}
+
+/// Derives how long a response may be cached from its `Cache-Control` and `Expires` headers.
+/// `Cache-Control` takes precedence; `no-store` and `no-cache` result in a lifetime of zero.
+/// Returns `None` if neither header specifies a lifetime.
+pub fn ttl_from_headers(
+    cache_control: Option<&str>,
+    expires: Option<&str>,
+    fetched_at: SystemTime,
+) -> Option<Duration> {
+    if let Some(cache_control) = cache_control {
+        let mut max_age = None;
+        for directive in cache_control.split(',') {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            if name.eq_ignore_ascii_case("no-store") || name.eq_ignore_ascii_case("no-cache") {
+                return Some(Duration::ZERO);
+            }
+            let shared = name.eq_ignore_ascii_case("s-maxage");
+            // s-maxage overrides max-age for shared caches like crawlers.
+            if (shared || (name.eq_ignore_ascii_case("max-age") && max_age.is_none()))
+                && let Some(secs) = value.and_then(|value| value.parse::<u64>().ok())
+            {
+                max_age = Some(Duration::from_secs(secs));
+            }
+        }
+        if max_age.is_some() {
+            return max_age;
+        }
+    }
+
+    // Invalid dates (like "0") are to be treated as already expired.
+    let expires = expires?;
+    Some(
+        parse_http_date(expires)
+            .and_then(|date| date.duration_since(fetched_at).ok())
+            .unwrap_or(Duration::ZERO),
+    )
 }
@@ -0,0 +1,133 @@
+use crate::Recogniser;
+
+/// The file names which are equivalent to the directory they are in, for [`ParseProfile::Yandex`].
+const INDEX_FILES: [&str; 2] = ["index.html", "index.htm"];
+
+/// How a `robots.txt` is interpreted in the edge cases where crawlers differ.
+///
+/// | | `Classic` | `Rfc9309` | `Google` | `Bing` | `Yandex` |
+/// |---|---|---|---|---|---|
+/// | Misspelled directives like `Dissallow` | ignored | ignored | accepted | ignored | ignored |
+/// | Lines without `:` like `Disallow /private` | ignored | ignored | accepted | ignored | ignored |
+/// | Values with spaces like `Disallow: /my page` | ignored | ignored | kept | ignored | ignored |
+/// | Rules before the first `User-agent` | ignored | ignored | ignored | apply like `*` | ignored |
+/// | Consecutive `User-agent` lines | last one applies | share the group | share the group | share the group | share the group |
+/// | `User-agent` naming the crawler | contains its name | equals its name | equals its name | contains its name | is a prefix of its name |
+/// | Empty `Allow` or `Disallow` | applies to every path | ignored | ignored | ignored | ignored |
+/// | Patterns not starting with `/` or `*` | taken as written | ignored | prefixed with `/` | prefixed with `/` | prefixed with `/` |
+/// | `Allow` and `Disallow` of the same pattern | last one applies | `Allow` applies | `Allow` applies | `Allow` applies | `Allow` applies |
+/// | `/dir/index.html` and `/dir/` | distinct | distinct | distinct | distinct | equivalent |
+/// | `Crawl-delay` | read | ignored | ignored | read | ignored |
+/// | `Request-rate` | read | ignored | ignored | ignored | ignored |
+/// | `Host` and `Clean-param` | read | ignored | ignored | ignored | read |
+///
+/// Names of user agents are compared ignoring case, except by `Classic`, which expects the user agent of the bot in lowercase.
+/// Google only reads the letters, `-` and `_` at the beginning of a `User-agent` line, so `Googlebot/2.1` names `googlebot`.
+/// `$` ends a pattern only as its last character and is taken literally elsewhere, and `*` matches anything everywhere, for every profile.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub enum ParseProfile {
+    /// The established behaviour of this crate.
+    #[default]
+    Classic,
+    /// The Robots Exclusion Protocol as specified by RFC 9309, without extensions.
+    Rfc9309,
+    /// The documented behaviour of Googlebot and Google's open source parser.
+    Google,
+    /// The documented behaviour of Bingbot.
+    Bing,
+    /// The documented behaviour of the Yandex crawlers, where `User-agent: Yandex` names every crawler starting with `Yandex`.
+    Yandex,
+}
+
+impl ParseProfile {
+    /// Returns how the profile recognises directives: Google corrects typos and missing colons and keeps values with spaces,
+    /// the others only read well-formed lines.
+    pub fn recogniser(&self) -> Recogniser {
+        match self {
+            ParseProfile::Google => Recogniser::lenient(),
+            _ => Recogniser::strict(),
+        }
+    }
+
+    /// Filters a directive and its value before it is applied.
+    /// Returns `None` if the profile ignores the directive.
+    pub(crate) fn prepare(&self, (op, val): (String, String)) -> Option<(String, String)> {
+        match op.as_str() {
+            "allow" | "disallow" if *self != ParseProfile::Classic => {
+                if val.is_empty() {
+                    None
+                } else if val.starts_with(['/', '*']) {
+                    Some((op, val))
+                } else if *self == ParseProfile::Rfc9309 {
+                    None
+                } else {
+                    Some((op, format!("/{val}")))
+                }
+            }
+            "crawl-delay" if !matches!(self, ParseProfile::Classic | ParseProfile::Bing) => None,
+            "request-rate" if *self != ParseProfile::Classic => None,
+            "host" | "clean-param"
+                if !matches!(self, ParseProfile::Classic | ParseProfile::Yandex) =>
+            {
+                None
+            }
+            _ => Some((op, val)),
+        }
+    }
+
+    /// Checks whether the value of a `User-agent` line names a user agent.
+    /// The user agent is expected in lowercase for [`ParseProfile::Classic`].
+    pub(crate) fn names(&self, line: &str, user_agent: &str) -> bool {
+        let line = line.trim();
+        match self {
+            ParseProfile::Classic => line.to_lowercase().contains(user_agent),
+            ParseProfile::Rfc9309 => line.eq_ignore_ascii_case(user_agent),
+            ParseProfile::Google => {
+                let end = line
+                    .find(|ch: char| !ch.is_ascii_alphabetic() && ch != '-' && ch != '_')
+                    .unwrap_or(line.len());
+                end > 0 && line[..end].eq_ignore_ascii_case(user_agent)
+            }
+            ParseProfile::Bing => line.to_lowercase().contains(&user_agent.to_lowercase()),
+            ParseProfile::Yandex => {
+                !line.is_empty() && user_agent.to_lowercase().starts_with(&line.to_lowercase())
+            }
+        }
+    }
+
+    /// Checks whether consecutive `User-agent` lines start a single group.
+    pub(crate) fn merges_user_agents(&self) -> bool {
+        *self != ParseProfile::Classic
+    }
+
+    /// Checks whether rules before the first `User-agent` line apply like those of a `*` group.
+    pub(crate) fn keeps_leading_rules(&self) -> bool {
+        *self == ParseProfile::Bing
+    }
+
+    /// Checks whether `Allow` takes precedence over `Disallow` with the same pattern.
+    pub(crate) fn prefers_allow(&self) -> bool {
+        *self != ParseProfile::Classic
+    }
+
+    /// Checks whether index files are equivalent to the directory they are in.
+    pub(crate) fn has_equivalent_paths(&self) -> bool {
+        *self == ParseProfile::Yandex
+    }
+
+    /// Returns the path equivalent to a path, like `/dir/` for `/dir/index.html`, if the profile knows one.
+    pub(crate) fn equivalent_path(&self, path: &str) -> Option<String> {
+        if !self.has_equivalent_paths() {
+            return None;
+        }
+        let end = path.find(['?', '#']).unwrap_or(path.len());
+        let (path, query) = path.split_at(end);
+        if path.ends_with('/') {
+            return Some(format!("{path}{}{query}", INDEX_FILES[0]));
+        }
+        INDEX_FILES.iter().find_map(|file| {
+            let dir = path.strip_suffix(file)?;
+            dir.ends_with('/').then(|| format!("{dir}{query}"))
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::Display;
+
+use crate::singlebot::{allows_spaces, is_directive};
+
+/// Misspelled directives found in real `robots.txt`s, along with the directive they stand for.
+/// Includes every typo accepted by Google's parser.
+const TYPOS: [(&str, &str); 14] = [
+    ("useragent", "user-agent"),
+    ("user agent", "user-agent"),
+    ("user_agent", "user-agent"),
+    ("user-agents", "user-agent"),
+    ("dissallow", "disallow"),
+    ("dissalow", "disallow"),
+    ("disalow", "disallow"),
+    ("diasllow", "disallow"),
+    ("disallaw", "disallow"),
+    ("dis-allow", "disallow"),
+    ("alow", "allow"),
+    ("crawldelay", "crawl-delay"),
+    ("crawl delay", "crawl-delay"),
+    ("site-map", "sitemap"),
+];
+
+/// Returns the directive a misspelled directive stands for, if it is a known typo.
+/// The directive is expected in lowercase, with single spaces between words.
+fn correct_typo(op: &str) -> Option<&'static str> {
+    TYPOS
+        .iter()
+        .find(|(typo, _)| *typo == op)
+        .map(|(_, directive)| *directive)
+}
+
+/// How values containing spaces are treated, like `Disallow: /my page`. `Content-Signal` values may always contain spaces.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub enum SpacePolicy {
+    /// The line is ignored.
+    #[default]
+    Ignore,
+    /// The value is kept as written.
+    Keep,
+    /// Only the first word of the value is kept.
+    FirstWord,
+}
+
+/// A correction made by a [`Recogniser`] to read a line.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Correction {
+    /// The directive was misspelled, as written.
+    Typo(String),
+    /// The directive and the value were separated by whitespace instead of `:`.
+    MissingColon,
+    /// The value contained spaces and was cut after its first word, as written.
+    Truncated(String),
+    /// The value contained spaces and was kept as written.
+    Spaces,
+}
+
+impl Display for Correction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Correction::Typo(written) => write!(f, "misspelled directive `{written}`"),
+            Correction::MissingColon => write!(f, "missing `:` after the directive"),
+            Correction::Truncated(written) => {
+                write!(
+                    f,
+                    "the value `{written}` contains spaces, only its first word is used"
+                )
+            }
+            Correction::Spaces => write!(f, "the value contains spaces and is kept as written"),
+        }
+    }
+}
+
+/// A line of a `robots.txt` read by a [`Recogniser`].
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct RecognisedLine {
+    /// The directive in lowercase, like `disallow`.
+    pub directive: String,
+    /// The value, trimmed.
+    pub value: String,
+    /// The corrections made to read the line, if any.
+    pub corrections: Vec<Correction>,
+}
+
+/// Recognises the directives of `robots.txt` lines, tolerating common mistakes as configured.
+///
+/// Directives are recognised ignoring case and whitespace around them, so `Allow :` is read like `Allow:` in any case.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub struct Recogniser {
+    /// Whether misspelled directives like `Dissallow` or `User agent` are corrected using a table of known typos.
+    pub typos: bool,
+    /// Whether lines without `:` are split at the first whitespace, like `Disallow /private`.
+    pub missing_colon: bool,
+    /// How values containing spaces are treated.
+    pub spaces: SpacePolicy,
+}
+
+impl Recogniser {
+    /// Creates a [`Recogniser`] which only reads well-formed lines.
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`Recogniser`] which corrects typos and missing colons and keeps values with spaces, like Google's parser.
+    pub fn lenient() -> Self {
+        Self {
+            typos: true,
+            missing_colon: true,
+            spaces: SpacePolicy::Keep,
+        }
+    }
+
+    /// Reads a line, which must not contain a comment.
+    /// Returns `None` if the line holds no known directive or is ignored.
+    pub fn recognise(&self, line: &str) -> Option<RecognisedLine> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        let mut corrections = Vec::new();
+        let directive = |op: &str, corrections: &mut Vec<Correction>| {
+            let op = op
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+            if is_directive(&op) {
+                return Some(op);
+            }
+            let directive = correct_typo(&op).filter(|_| self.typos)?;
+            corrections.push(Correction::Typo(op));
+            Some(directive.to_owned())
+        };
+
+        let colon = line
+            .split_once(':')
+            .and_then(|(op, val)| Some((directive(op, &mut corrections)?, val)));
+        let (directive, value) = match colon {
+            Some(pair) => pair,
+            None if self.missing_colon => {
+                let (op, val) = line.split_once(char::is_whitespace)?;
+                let directive = directive(op, &mut corrections)?;
+                corrections.push(Correction::MissingColon);
+                (directive, val)
+            }
+            None => return None,
+        };
+
+        let mut value = value.trim().to_owned();
+        if !allows_spaces(&directive) && value.contains(' ') {
+            match self.spaces {
+                SpacePolicy::Ignore => return None,
+                SpacePolicy::Keep => corrections.push(Correction::Spaces),
+                SpacePolicy::FirstWord => {
+                    let first = value.split(' ').next().unwrap_or_default().to_owned();
+                    corrections.push(Correction::Truncated(std::mem::replace(&mut value, first)));
+                }
+            }
+        }
+        Some(RecognisedLine {
+            directive,
+            value,
+            corrections,
+        })
+    }
+}
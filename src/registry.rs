@@ -0,0 +1,289 @@
+use std::fmt::Display;
+
+use crate::{AgentBot, Permission, SingleBot};
+
+/// What a crawler collects content for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub enum CrawlerCategory {
+    /// Builds the index of a search engine.
+    Search,
+    /// Collects content to train AI models.
+    AiTraining,
+    /// Builds the index of an AI search or answer engine.
+    AiSearch,
+    /// Fetches pages on behalf of a user of an AI assistant.
+    AiAssistant,
+    /// Archives pages for posterity.
+    Archive,
+    /// Collects links and rankings for search engine optimisation tools.
+    Seo,
+    /// Fetches previews of links shared on social networks and messengers.
+    SocialPreview,
+    /// Any other purpose.
+    Other,
+}
+
+/// The names of the categories, as used by [`CrawlerCategory::parse`] and [`Display`].
+const CATEGORY_NAMES: [(CrawlerCategory, &str); 8] = [
+    (CrawlerCategory::Search, "search"),
+    (CrawlerCategory::AiTraining, "ai-training"),
+    (CrawlerCategory::AiSearch, "ai-search"),
+    (CrawlerCategory::AiAssistant, "ai-assistant"),
+    (CrawlerCategory::Archive, "archive"),
+    (CrawlerCategory::Seo, "seo"),
+    (CrawlerCategory::SocialPreview, "social-preview"),
+    (CrawlerCategory::Other, "other"),
+];
+
+impl CrawlerCategory {
+    /// Parses the name of a category like `ai-training`, ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        CATEGORY_NAMES
+            .iter()
+            .find(|(_, category)| category.eq_ignore_ascii_case(name.trim()))
+            .map(|(category, _)| *category)
+    }
+
+    /// Checks whether crawlers of the category use content for AI models.
+    pub fn is_ai(&self) -> bool {
+        matches!(
+            self,
+            CrawlerCategory::AiTraining | CrawlerCategory::AiSearch | CrawlerCategory::AiAssistant
+        )
+    }
+}
+
+impl Display for CrawlerCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = CATEGORY_NAMES
+            .iter()
+            .find(|(category, _)| category == self)
+            .map_or("other", |(_, name)| name);
+        write!(f, "{name}")
+    }
+}
+
+/// A crawler known by its product token, i.e. the name it is addressed by in `User-agent` lines.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Crawler {
+    /// The product token, e.g. `GPTBot`.
+    pub token: String,
+    /// The organisation operating the crawler.
+    pub organisation: String,
+    /// What the crawler collects content for.
+    pub category: CrawlerCategory,
+}
+
+impl Crawler {
+    /// Creates a new [`Crawler`].
+    pub fn new(token: &str, organisation: &str, category: CrawlerCategory) -> Self {
+        Self {
+            token: token.to_owned(),
+            organisation: organisation.to_owned(),
+            category,
+        }
+    }
+
+    /// Returns the user agent to create bots with, i.e. the product token in lowercase.
+    pub fn user_agent(&self) -> String {
+        self.token.to_lowercase()
+    }
+
+    /// Creates an empty [`SingleBot`] for the crawler, to add `robots.txt`s to.
+    pub fn bot(&self) -> SingleBot {
+        SingleBot::new(Some(self.user_agent()))
+    }
+}
+
+/// The crawlers known by [`CrawlerRegistry::builtin`].
+const BUILTIN: [(&str, &str, CrawlerCategory); 40] = [
+    ("Googlebot", "Google", CrawlerCategory::Search),
+    ("Bingbot", "Microsoft", CrawlerCategory::Search),
+    ("Applebot", "Apple", CrawlerCategory::Search),
+    ("DuckDuckBot", "DuckDuckGo", CrawlerCategory::Search),
+    ("YandexBot", "Yandex", CrawlerCategory::Search),
+    ("Baiduspider", "Baidu", CrawlerCategory::Search),
+    ("Slurp", "Yahoo", CrawlerCategory::Search),
+    ("SeznamBot", "Seznam", CrawlerCategory::Search),
+    ("Yeti", "Naver", CrawlerCategory::Search),
+    ("Sogou", "Sogou", CrawlerCategory::Search),
+    ("GPTBot", "OpenAI", CrawlerCategory::AiTraining),
+    ("CCBot", "Common Crawl", CrawlerCategory::AiTraining),
+    ("ClaudeBot", "Anthropic", CrawlerCategory::AiTraining),
+    ("anthropic-ai", "Anthropic", CrawlerCategory::AiTraining),
+    ("Google-Extended", "Google", CrawlerCategory::AiTraining),
+    ("Applebot-Extended", "Apple", CrawlerCategory::AiTraining),
+    ("Bytespider", "ByteDance", CrawlerCategory::AiTraining),
+    ("Meta-ExternalAgent", "Meta", CrawlerCategory::AiTraining),
+    ("Amazonbot", "Amazon", CrawlerCategory::AiTraining),
+    ("cohere-ai", "Cohere", CrawlerCategory::AiTraining),
+    ("Diffbot", "Diffbot", CrawlerCategory::AiTraining),
+    ("Omgilibot", "Webz.io", CrawlerCategory::AiTraining),
+    ("OAI-SearchBot", "OpenAI", CrawlerCategory::AiSearch),
+    ("Claude-SearchBot", "Anthropic", CrawlerCategory::AiSearch),
+    ("PerplexityBot", "Perplexity", CrawlerCategory::AiSearch),
+    ("ChatGPT-User", "OpenAI", CrawlerCategory::AiAssistant),
+    ("Claude-User", "Anthropic", CrawlerCategory::AiAssistant),
+    (
+        "Perplexity-User",
+        "Perplexity",
+        CrawlerCategory::AiAssistant,
+    ),
+    ("ia_archiver", "Alexa Internet", CrawlerCategory::Archive),
+    (
+        "archive.org_bot",
+        "Internet Archive",
+        CrawlerCategory::Archive,
+    ),
+    ("AhrefsBot", "Ahrefs", CrawlerCategory::Seo),
+    ("SemrushBot", "Semrush", CrawlerCategory::Seo),
+    ("MJ12bot", "Majestic", CrawlerCategory::Seo),
+    ("DotBot", "Moz", CrawlerCategory::Seo),
+    (
+        "facebookexternalhit",
+        "Meta",
+        CrawlerCategory::SocialPreview,
+    ),
+    ("Twitterbot", "X", CrawlerCategory::SocialPreview),
+    ("LinkedInBot", "LinkedIn", CrawlerCategory::SocialPreview),
+    ("Slackbot", "Slack", CrawlerCategory::SocialPreview),
+    ("Discordbot", "Discord", CrawlerCategory::SocialPreview),
+    ("TelegramBot", "Telegram", CrawlerCategory::SocialPreview),
+];
+
+/// Error if a line of a crawler list is invalid.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RegistryError {
+    /// The number of the invalid line, starting at 1.
+    pub line: usize,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// A list of known crawlers, looked up by product token ignoring case.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CrawlerRegistry {
+    crawlers: Vec<Crawler>,
+}
+
+impl CrawlerRegistry {
+    /// Creates an empty [`CrawlerRegistry`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`CrawlerRegistry`] of well-known search, AI, archive, SEO and social network crawlers.
+    pub fn builtin() -> Self {
+        Self {
+            crawlers: BUILTIN
+                .iter()
+                .map(|(token, organisation, category)| Crawler::new(token, organisation, *category))
+                .collect(),
+        }
+    }
+
+    /// Adds a crawler, replacing a known crawler with the same product token.
+    pub fn add(&mut self, crawler: Crawler) {
+        match self
+            .crawlers
+            .iter_mut()
+            .find(|known| known.token.eq_ignore_ascii_case(&crawler.token))
+        {
+            Some(known) => *known = crawler,
+            None => self.crawlers.push(crawler),
+        }
+    }
+
+    /// Adds the crawlers of a list with one `token, organisation, category` per line, like `GPTBot, OpenAI, ai-training`.
+    /// Empty lines and comments starting with `#` are skipped.
+    /// # Errors
+    /// Will return `Err` for the first invalid line. The crawlers of the lines before it are added.
+    pub fn add_list(&mut self, list: &str) -> Result<(), RegistryError> {
+        for (idx, line) in list.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| RegistryError {
+                line: idx + 1,
+                message,
+            };
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [token, organisation, category] = fields[..] else {
+                return Err(error(format!(
+                    "expected `token, organisation, category`, found `{line}`"
+                )));
+            };
+            if token.is_empty() || token.contains(char::is_whitespace) {
+                return Err(error(format!("invalid product token `{token}`")));
+            }
+            let category = CrawlerCategory::parse(category)
+                .ok_or_else(|| error(format!("unknown category `{category}`")))?;
+            self.add(Crawler::new(token, organisation, category));
+        }
+        Ok(())
+    }
+
+    /// Forgets a crawler.
+    /// Returns `false` if the crawler wasn't known.
+    pub fn remove(&mut self, token: &str) -> bool {
+        let len = self.crawlers.len();
+        self.crawlers
+            .retain(|crawler| !crawler.token.eq_ignore_ascii_case(token));
+        self.crawlers.len() != len
+    }
+
+    /// Gets a crawler by its product token.
+    pub fn get(&self, token: &str) -> Option<&Crawler> {
+        self.crawlers
+            .iter()
+            .find(|crawler| crawler.token.eq_ignore_ascii_case(token))
+    }
+
+    /// Iterates over the known crawlers, in order of addition.
+    pub fn iter(&self) -> impl Iterator<Item = &Crawler> {
+        self.crawlers.iter()
+    }
+
+    /// Iterates over the known crawlers of a category.
+    pub fn by_category(&self, category: CrawlerCategory) -> impl Iterator<Item = &Crawler> {
+        self.crawlers
+            .iter()
+            .filter(move |crawler| crawler.category == category)
+    }
+
+    /// Returns the number of known crawlers.
+    pub fn len(&self) -> usize {
+        self.crawlers.len()
+    }
+
+    /// Returns `true` if no crawler is known.
+    pub fn is_empty(&self) -> bool {
+        self.crawlers.is_empty()
+    }
+
+    /// Checks a path for every known crawler.
+    pub fn check(&self, bot: &AgentBot, path: &str) -> Vec<(&Crawler, Permission)> {
+        self.crawlers
+            .iter()
+            .map(|crawler| (crawler, bot.check(&crawler.token, path)))
+            .collect()
+    }
+
+    /// Returns the known crawlers which may not crawl the root of a site.
+    pub fn blocked(&self, bot: &AgentBot) -> Vec<&Crawler> {
+        self.check(bot, "/")
+            .into_iter()
+            .filter(|(_, permission)| *permission == Permission::Denied)
+            .map(|(crawler, _)| crawler)
+            .collect()
+    }
+}
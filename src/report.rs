@@ -0,0 +1,196 @@
+use std::{
+    fmt::{Display, Write},
+    time::Duration,
+};
+
+use url::Host;
+
+use crate::{AgentView, ContentSignals, MetaBot, Permission};
+
+/// How much of a site a user agent may crawl.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub enum Access {
+    /// `/` may be crawled and no rule disallows anything.
+    Allowed,
+    /// Some paths are disallowed, or `/` is disallowed but some paths are allowed.
+    PartiallyBlocked,
+    /// `/` is disallowed and no rule allows anything.
+    Blocked,
+}
+
+impl Access {
+    /// Classifies the rules applying to a user agent.
+    pub fn of(view: &AgentView<'_>) -> Self {
+        let has_rule = |permission| view.rules().iter().any(|(_, other)| *other == permission);
+        match view.check("/") {
+            Permission::Denied if has_rule(Permission::Allowed) => Access::PartiallyBlocked,
+            Permission::Denied => Access::Blocked,
+            _ if has_rule(Permission::Denied) => Access::PartiallyBlocked,
+            _ => Access::Allowed,
+        }
+    }
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Access::Allowed => write!(f, "allowed"),
+            Access::PartiallyBlocked => write!(f, "partially-blocked"),
+            Access::Blocked => write!(f, "blocked"),
+        }
+    }
+}
+
+/// The policy of one host for one user agent.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PolicyEntry {
+    /// The host whose `robots.txt` defines the policy.
+    pub host: Host<String>,
+    /// The user agent the policy applies to, as given.
+    pub agent: String,
+    /// How much of the host the user agent may crawl.
+    pub access: Access,
+    /// The delay between two requests, as specified by `Crawl-delay`.
+    pub crawl_delay: Option<Duration>,
+    /// How content may be used, as specified by `Content-Signal`.
+    pub content_signals: Option<ContentSignals>,
+}
+
+/// The policies of every host of a [`MetaBot`] for a list of user agents, exportable as CSV or JSON.
+///
+/// Policies are taken from the known `robots.txt`s, regardless of whether they have expired.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PolicyReport {
+    /// The policies ordered by host, then by user agent in the order given.
+    pub entries: Vec<PolicyEntry>,
+}
+
+impl PolicyReport {
+    /// Reports the policies of every host of `meta` for every user agent of `agents`.
+    pub fn new<'a>(meta: &MetaBot, agents: impl IntoIterator<Item = &'a str>) -> Self {
+        let agents: Vec<&str> = agents.into_iter().collect();
+        let mut hosts: Vec<&Host<String>> = meta.hosts().collect();
+        hosts.sort_by_cached_key(|host| host.to_string());
+
+        let mut entries = Vec::with_capacity(hosts.len() * agents.len());
+        for host in hosts {
+            for agent in &agents {
+                let Some(view) = meta.get_as(host, agent) else {
+                    continue;
+                };
+                entries.push(PolicyEntry {
+                    host: host.clone(),
+                    agent: (*agent).to_owned(),
+                    access: Access::of(&view),
+                    crawl_delay: view.crawl_delay(),
+                    content_signals: view.content_signals(),
+                });
+            }
+        }
+        Self { entries }
+    }
+
+    /// Iterates over the policies of a user agent, ignoring case.
+    pub fn for_agent<'a>(&'a self, agent: &'a str) -> impl Iterator<Item = &'a PolicyEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.agent.eq_ignore_ascii_case(agent))
+    }
+
+    /// Exports the report as CSV with the columns `host,agent,access,crawl_delay,content_signal`.
+    /// Crawl delays are given in seconds and content signals like `search=yes, ai-train=no`. Missing values are left empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("host,agent,access,crawl_delay,content_signal\n");
+        for entry in &self.entries {
+            let fields = [
+                entry.host.to_string(),
+                entry.agent.clone(),
+                entry.access.to_string(),
+                entry
+                    .crawl_delay
+                    .map(|delay| delay.as_secs_f64().to_string())
+                    .unwrap_or_default(),
+                entry
+                    .content_signals
+                    .map(|signals| signals.to_string())
+                    .unwrap_or_default(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Exports the report as a JSON array with one object per policy.
+    /// Content signals are objects with the uses `search`, `ai-input` and `ai-train` as booleans. Missing values are `null`.
+    pub fn to_json(&self) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let crawl_delay = entry
+                    .crawl_delay
+                    .map_or_else(|| "null".to_owned(), |delay| delay.as_secs_f64().to_string());
+                let content_signals = entry.content_signals.map_or_else(
+                    || "null".to_owned(),
+                    |signals| {
+                        format!(
+                            r#"{{"search":{},"ai-input":{},"ai-train":{}}}"#,
+                            json_bool(signals.search),
+                            json_bool(signals.ai_input),
+                            json_bool(signals.ai_train)
+                        )
+                    },
+                );
+                format!(
+                    r#"{{"host":{},"agent":{},"access":"{}","crawl_delay":{},"content_signal":{}}}"#,
+                    json_string(&entry.host.to_string()),
+                    json_string(&entry.agent),
+                    entry.access,
+                    crawl_delay,
+                    content_signals
+                )
+            })
+            .collect();
+        format!("[{}]", entries.join(","))
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(val: &str) -> String {
+    if val.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", val.replace('"', "\"\""))
+    } else {
+        val.to_owned()
+    }
+}
+
+/// Quotes a string as a JSON string, escaping quotes, backslashes and control characters.
+pub fn json_string(val: &str) -> String {
+    let mut out = String::with_capacity(val.len() + 2);
+    out.push('"');
+    for ch in val.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch < ' ' => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_bool(val: Option<bool>) -> &'static str {
+    match val {
+        Some(true) => "true",
+        Some(false) => "false",
+        None => "null",
+    }
+}
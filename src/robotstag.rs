@@ -0,0 +1,301 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    html::tags,
+    httpdate::{days_from_civil, parse_http_date},
+};
+
+/// How large image previews may be shown, as specified by `max-image-preview`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub enum ImagePreview {
+    /// No image preview may be shown.
+    Disabled,
+    /// A default image preview may be shown.
+    Standard,
+    /// A larger image preview may be shown.
+    Large,
+}
+
+/// The indexing directives given to a page by robots meta tags and `X-Robots-Tag` headers.
+///
+/// If directives are given more than once, the most restrictive one applies.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub struct IndexingDirectives {
+    /// The page must not be indexed (`noindex` or `none`).
+    pub noindex: bool,
+    /// The links of the page must not be followed (`nofollow` or `none`).
+    pub nofollow: bool,
+    /// No cached copy of the page may be shown (`noarchive` or `nocache`).
+    pub noarchive: bool,
+    /// No snippet of the page may be shown (`nosnippet`).
+    pub nosnippet: bool,
+    /// The images of the page must not be indexed (`noimageindex`).
+    pub noimageindex: bool,
+    /// No translation of the page may be offered (`notranslate`).
+    pub notranslate: bool,
+    /// The page may be indexed when embedded in another page, despite `noindex` (`indexifembedded`).
+    pub indexifembedded: bool,
+    /// The longest snippet in characters (`max-snippet`). `None` if unlimited.
+    pub max_snippet: Option<u32>,
+    /// The largest image preview (`max-image-preview`). `None` if unspecified.
+    pub max_image_preview: Option<ImagePreview>,
+    /// The longest video preview in seconds (`max-video-preview`). `None` if unlimited.
+    pub max_video_preview: Option<u32>,
+    /// The time after which the page must not be shown anymore (`unavailable_after`).
+    pub unavailable_after: Option<SystemTime>,
+}
+
+impl IndexingDirectives {
+    /// Parses a comma-separated list of directives, like the content of a robots meta tag. Unknown directives are ignored.
+    pub fn parse(list: &str) -> Self {
+        Self::parse_known(list).unwrap_or_default()
+    }
+
+    /// Parses a list of directives, returning `None` if none is known.
+    fn parse_known(list: &str) -> Option<Self> {
+        let mut directives = Self::default();
+        let mut known = false;
+        for item in split_directives(list) {
+            known |= directives.apply(&item);
+        }
+        known.then_some(directives)
+    }
+
+    /// Checks whether the page may be indexed at `now`.
+    pub fn allows_indexing(&self, now: SystemTime) -> bool {
+        !self.noindex && self.unavailable_after.is_none_or(|after| now < after)
+    }
+
+    /// Adds the directives of another set, keeping the most restrictive ones.
+    pub fn merge(&mut self, other: &IndexingDirectives) {
+        self.noindex |= other.noindex;
+        self.nofollow |= other.nofollow;
+        self.noarchive |= other.noarchive;
+        self.nosnippet |= other.nosnippet;
+        self.noimageindex |= other.noimageindex;
+        self.notranslate |= other.notranslate;
+        self.indexifembedded |= other.indexifembedded;
+        self.max_snippet = min_limit(self.max_snippet, other.max_snippet);
+        self.max_image_preview = min_limit(self.max_image_preview, other.max_image_preview);
+        self.max_video_preview = min_limit(self.max_video_preview, other.max_video_preview);
+        self.unavailable_after = min_limit(self.unavailable_after, other.unavailable_after);
+    }
+
+    /// Applies a directive, returning `false` if it is unknown.
+    fn apply(&mut self, directive: &str) -> bool {
+        let (name, value) = match directive.split_once(':') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (directive.trim(), None),
+        };
+        let mut other = IndexingDirectives::default();
+        match (name.to_ascii_lowercase().as_str(), value) {
+            ("all" | "index" | "follow", _) => {}
+            ("noindex", _) => other.noindex = true,
+            ("nofollow", _) => other.nofollow = true,
+            ("none", _) => {
+                other.noindex = true;
+                other.nofollow = true;
+            }
+            ("noarchive" | "nocache", _) => other.noarchive = true,
+            ("nosnippet", _) => other.nosnippet = true,
+            ("noimageindex", _) => other.noimageindex = true,
+            ("notranslate", _) => other.notranslate = true,
+            ("indexifembedded", _) => other.indexifembedded = true,
+            ("max-snippet", Some(value)) => other.max_snippet = parse_limit(value),
+            ("max-video-preview", Some(value)) => other.max_video_preview = parse_limit(value),
+            ("max-image-preview", Some(value)) => {
+                other.max_image_preview = match value.to_ascii_lowercase().as_str() {
+                    "none" => Some(ImagePreview::Disabled),
+                    "standard" => Some(ImagePreview::Standard),
+                    "large" => Some(ImagePreview::Large),
+                    _ => None,
+                }
+            }
+            ("unavailable_after", Some(value)) => other.unavailable_after = parse_date(value),
+            _ => return false,
+        }
+        self.merge(&other);
+        true
+    }
+}
+
+/// Parses a limit like `max-snippet`, where `-1` means unlimited.
+fn parse_limit(value: &str) -> Option<u32> {
+    value.parse().ok()
+}
+
+/// Returns the more restrictive of two limits, where `None` means unlimited.
+fn min_limit<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// The names of the directives which take a value after a `:`, so a name before a `:` isn't a user agent.
+const VALUE_DIRECTIVES: [&str; 4] = [
+    "max-snippet",
+    "max-image-preview",
+    "max-video-preview",
+    "unavailable_after",
+];
+
+fn is_value_directive(name: &str) -> bool {
+    VALUE_DIRECTIVES
+        .iter()
+        .any(|directive| directive.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Splits a list of directives at commas, keeping dates like `Wednesday, 03-Nov-2021 15:00:00 GMT` in one piece.
+fn split_directives(list: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for item in list.split(',') {
+        if let Some(last) = items.last_mut()
+            && let Some(idx) = last.to_ascii_lowercase().find("unavailable_after:")
+            && let value = &last[idx + "unavailable_after:".len()..]
+            && parse_date(value).is_none()
+            && parse_date(&format!("{value},{item}")).is_some()
+        {
+            last.push(',');
+            last.push_str(item);
+            continue;
+        }
+        if !item.trim().is_empty() {
+            items.push(item.to_owned());
+        }
+    }
+    items
+}
+
+/// Parses the date of `unavailable_after`, either in ISO 8601 or as in RFC 822 or RFC 850.
+/// Time zones other than UTC offsets of ISO 8601 are treated like GMT.
+fn parse_date(value: &str) -> Option<SystemTime> {
+    let value = value.trim();
+    if let Some(date) = parse_iso_date(value) {
+        return Some(date);
+    }
+    // RFC 822 dates may omit the weekday, which the HTTP date parser skips.
+    parse_http_date(value).or_else(|| parse_http_date(&format!("_, {value}")))
+}
+
+/// Parses an ISO 8601 date like `2025-12-31` or `2025-12-31T23:59:59+01:00`.
+fn parse_iso_date(value: &str) -> Option<SystemTime> {
+    let (date, time) = match value.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut secs = days_from_civil(year, month, day)?.checked_mul(86_400)?;
+    if let Some(time) = time {
+        let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+            Some(idx) => time.split_at(idx),
+            None => (time, ""),
+        };
+        let mut clock = clock.splitn(3, ':');
+        let hours: i64 = clock.next()?.parse().ok()?;
+        let minutes: i64 = clock.next().map_or(Some(0), |part| part.parse().ok())?;
+        let seconds: f64 = clock.next().map_or(Some(0.0), |part| part.parse().ok())?;
+        let clock = hours
+            .checked_mul(3_600)?
+            .checked_add(minutes.checked_mul(60)?)?
+            .checked_add(seconds as i64)?;
+        secs = secs.checked_add(clock)?;
+        if let Some(offset) = offset.strip_prefix(['+', '-']) {
+            let (offset_hours, offset_minutes) = offset.split_once(':').unwrap_or((offset, "0"));
+            let offset = offset_hours
+                .parse::<i64>()
+                .ok()?
+                .checked_mul(3_600)?
+                .checked_add(offset_minutes.parse::<i64>().ok()?.checked_mul(60)?)?;
+            secs = if time.contains('-') {
+                secs.checked_add(offset)?
+            } else {
+                secs.checked_sub(offset)?
+            };
+        }
+    }
+    UNIX_EPOCH.checked_add(Duration::from_secs(u64::try_from(secs).ok()?))
+}
+
+/// The indexing directives of a page as seen by one user agent, collected from robots meta tags and `X-Robots-Tag` headers.
+///
+/// Like [`crate::SingleBot`] selects groups, directives naming the user agent replace the generic ones.
+/// Without user agent, only the generic directives apply.
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub struct PageRobots {
+    user_agent: Option<String>,
+    generic: IndexingDirectives,
+    named: Option<IndexingDirectives>,
+}
+
+impl PageRobots {
+    /// Creates a new [`PageRobots`] for a user agent, e.g. `googlebot`.
+    pub fn new(user_agent: Option<String>) -> Self {
+        Self {
+            user_agent: user_agent.map(|user_agent| user_agent.to_lowercase()),
+            ..Default::default()
+        }
+    }
+
+    /// Adds the value of an `X-Robots-Tag` header, like `noindex, nofollow` or `googlebot: noindex`.
+    pub fn add_header(&mut self, value: &str) {
+        // A user agent applies to the directives following it, until the next user agent.
+        let mut scope: Option<String> = None;
+        for item in split_directives(value) {
+            let directive = match item.split_once(':') {
+                Some((name, directive)) if !is_value_directive(name) => {
+                    scope = Some(name.trim().to_owned());
+                    directive.to_owned()
+                }
+                _ => item,
+            };
+            if let Some(directives) = IndexingDirectives::parse_known(&directive) {
+                self.add(scope.as_deref(), &directives);
+            }
+        }
+    }
+
+    /// Adds a meta tag, like `<meta name="robots" content="noindex">` or `<meta name="googlebot" content="nosnippet">`.
+    /// Tags with other names than `robots` are taken as naming a user agent, unless their content holds no known directive.
+    pub fn add_meta(&mut self, name: &str, content: &str) {
+        let name = name.trim();
+        let scope = (!name.eq_ignore_ascii_case("robots")).then_some(name);
+        if let Some(directives) = IndexingDirectives::parse_known(content) {
+            self.add(scope, &directives);
+        }
+    }
+
+    /// Adds the robots meta tags of an HTML document.
+    pub fn add_html(&mut self, html: &str) {
+        for tag in tags(html).filter(|tag| tag.name == "meta") {
+            if let (Some(name), Some(content)) = (tag.attribute("name"), tag.attribute("content")) {
+                self.add_meta(name, content);
+            }
+        }
+    }
+
+    fn add(&mut self, scope: Option<&str>, directives: &IndexingDirectives) {
+        match scope {
+            None => self.generic.merge(directives),
+            Some(name) => {
+                if let Some(user_agent) = &self.user_agent
+                    && name.to_lowercase().contains(user_agent.as_str())
+                {
+                    self.named.get_or_insert_default().merge(directives);
+                }
+            }
+        }
+    }
+
+    /// Returns the directives applying to the user agent.
+    pub fn directives(&self) -> IndexingDirectives {
+        self.named.unwrap_or(self.generic)
+    }
+}
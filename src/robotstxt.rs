@@ -0,0 +1,244 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{
+    CleanParam, ContentSignals, ParseProfile, Permission, RequestRate, SingleBot,
+    singlebot::{decode_pattern, merge_content_signals, parse_crawl_delay, strip_comment_str},
+};
+
+/// An `Allow` or `Disallow` rule of a [`Group`].
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Rule {
+    /// The permission given by the rule, either [`Permission::Allowed`] or [`Permission::Denied`].
+    pub permission: Permission,
+    /// The path pattern of the rule, as written or as adjusted by the profile, like the `/` prepended by [`ParseProfile::Google`].
+    pub pattern: String,
+    /// The number of the line of the rule, starting at 1.
+    pub line: usize,
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let directive = match self.permission {
+            Permission::Allowed => "Allow",
+            _ => "Disallow",
+        };
+        write!(f, "{directive}: {}", self.pattern)
+    }
+}
+
+impl Rule {
+    /// Returns a path matched by the rule, with the wildcards matching nothing.
+    pub fn example_path(&self) -> String {
+        let key = decode_pattern(self.pattern.clone());
+        key.split('\0').next().unwrap_or_default().replace('*', "")
+    }
+}
+
+/// A group of a `robots.txt`: the user agents it applies to and their rules.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Group {
+    /// The user agents of the group, as written. `*` applies to every user agent.
+    pub user_agents: Vec<String>,
+    /// The rules of the group, in order of appearance.
+    pub rules: Vec<Rule>,
+    /// The delay between two requests, as specified by the first `Crawl-delay` directive.
+    pub crawl_delay: Option<Duration>,
+    /// The request rate, as specified by the first `Request-rate` directive.
+    pub request_rate: Option<RequestRate>,
+    /// How content may be used, as specified by the first `Content-Signal` directive mentioning each use.
+    pub content_signals: Option<ContentSignals>,
+    /// The number of the line of the first `User-agent` of the group, starting at 1.
+    pub line: usize,
+}
+
+impl Group {
+    /// Checks whether the group applies to every user agent.
+    pub fn is_star(&self) -> bool {
+        self.user_agents.iter().any(|agent| agent == "*")
+    }
+
+    /// Checks whether the group names a user agent, comparing names like `profile` does but ignoring case.
+    /// `*` isn't considered a name.
+    pub fn names(&self, user_agent: &str, profile: ParseProfile) -> bool {
+        let user_agent = user_agent.to_lowercase();
+        self.user_agents
+            .iter()
+            .any(|agent| agent != "*" && profile.names(agent, &user_agent))
+    }
+
+    fn apply(&mut self, op: &str, val: String, line: usize) {
+        match op {
+            "allow" | "disallow" => self.rules.push(Rule {
+                permission: if op == "allow" {
+                    Permission::Allowed
+                } else {
+                    Permission::Denied
+                },
+                pattern: val,
+                line,
+            }),
+            "crawl-delay" if self.crawl_delay.is_none() => {
+                self.crawl_delay = parse_crawl_delay(&val);
+            }
+            "request-rate" if self.request_rate.is_none() => {
+                self.request_rate = RequestRate::parse(&val);
+            }
+            "content-signal" => merge_content_signals(&mut self.content_signals, &val),
+            _ => {}
+        }
+    }
+}
+
+/// A parsed `robots.txt` which keeps its groups apart, unlike [`crate::SingleBot`] which only keeps the rules for one user agent.
+///
+/// Lines are recognised, filtered and grouped by the same rules as a [`crate::SingleBot`] with the same [`ParseProfile`],
+/// so [`RobotsTxt::bot`] decides like that bot. Only a `User-agent` line directly following another one may join its group,
+/// and only if the profile merges them. With [`ParseProfile::Bing`], rules before the first `User-agent` form a `*` group,
+/// starting at the first of them.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RobotsTxt {
+    /// The groups, in order of appearance.
+    pub groups: Vec<Group>,
+    /// The locations listed by `Sitemap` directives, without duplicates.
+    pub sitemaps: Vec<String>,
+    /// The preferred mirror of the site, as specified by the first `Host` directive.
+    pub host: Option<String>,
+    /// The `Clean-param` directives, without duplicates.
+    pub clean_params: Vec<CleanParam>,
+    /// The profile the `robots.txt` was read with, which also decides how user agents are matched.
+    pub profile: ParseProfile,
+}
+
+impl RobotsTxt {
+    /// Parses a `robots.txt` with the default [`ParseProfile`], like [`crate::SingleBot::from`] does.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with_profile(input, ParseProfile::default())
+    }
+
+    /// Parses a `robots.txt` the way the crawlers of a [`ParseProfile`] do.
+    pub fn parse_with_profile(input: &str, profile: ParseProfile) -> Self {
+        let mut robots = RobotsTxt {
+            profile,
+            ..Default::default()
+        };
+        let recogniser = profile.recogniser();
+        // Whether the previous directive was a `User-agent`, so another one may extend the group.
+        let mut after_user_agent = false;
+
+        for (idx, line) in input.lines().enumerate() {
+            let line_number = idx + 1;
+            let Some(recognised) = recogniser.recognise(strip_comment_str(line)) else {
+                continue;
+            };
+            let Some((op, val)) = profile.prepare((recognised.directive, recognised.value)) else {
+                continue;
+            };
+            let extends_group = std::mem::replace(&mut after_user_agent, op == "user-agent")
+                && profile.merges_user_agents();
+
+            match op.as_str() {
+                "user-agent" => match robots.groups.last_mut() {
+                    Some(group) if extends_group => group.user_agents.push(val),
+                    _ => robots.groups.push(Group {
+                        user_agents: vec![val],
+                        line: line_number,
+                        ..Default::default()
+                    }),
+                },
+                "sitemap" => {
+                    if !robots.sitemaps.contains(&val) {
+                        robots.sitemaps.push(val);
+                    }
+                }
+                "host" => {
+                    if robots.host.is_none() && !val.is_empty() {
+                        robots.host = Some(val);
+                    }
+                }
+                "clean-param" => {
+                    if let Some(clean_param) = CleanParam::parse(&val)
+                        && !robots.clean_params.contains(&clean_param)
+                    {
+                        robots.clean_params.push(clean_param);
+                    }
+                }
+                _ => {
+                    if robots.groups.is_empty() && profile.keeps_leading_rules() {
+                        robots.groups.push(Group {
+                            user_agents: vec!["*".to_owned()],
+                            line: line_number,
+                            ..Default::default()
+                        });
+                    }
+                    if let Some(group) = robots.groups.last_mut() {
+                        group.apply(&op, val, line_number);
+                    }
+                }
+            }
+        }
+
+        robots
+    }
+
+    /// Returns the groups applying to a user agent: the groups naming it, or the `*` groups if none does.
+    /// Without user agent, only the `*` groups apply.
+    pub fn groups_for(&self, user_agent: Option<&str>) -> Vec<&Group> {
+        if let Some(user_agent) = user_agent {
+            let named: Vec<_> = self
+                .groups
+                .iter()
+                .filter(|group| group.names(user_agent, self.profile))
+                .collect();
+            if !named.is_empty() {
+                return named;
+            }
+        }
+        self.groups.iter().filter(|group| group.is_star()).collect()
+    }
+
+    /// Creates a [`SingleBot`] with the same profile from the groups applying to a user agent, see [`RobotsTxt::groups_for`].
+    pub fn bot(&self, user_agent: Option<&str>) -> SingleBot {
+        let mut bot = SingleBot::with_profile(user_agent.map(str::to_lowercase), self.profile);
+        for group in self.groups_for(user_agent) {
+            for rule in &group.rules {
+                let op = match rule.permission {
+                    Permission::Allowed => "allow",
+                    _ => "disallow",
+                };
+                bot.apply(SingleBot::decode((op.to_owned(), rule.pattern.clone())));
+            }
+            if let Some(delay) = group.crawl_delay {
+                bot.apply(("crawl-delay".to_owned(), delay.as_secs_f64().to_string()));
+            }
+            if let Some(rate) = group.request_rate {
+                let per = rate.per.as_secs();
+                bot.apply((
+                    "request-rate".to_owned(),
+                    format!("{}/{per}", rate.requests),
+                ));
+            }
+            if let Some(signals) = group.content_signals {
+                bot.apply(("content-signal".to_owned(), signals.to_string()));
+            }
+        }
+        for sitemap in &self.sitemaps {
+            bot.apply(("sitemap".to_owned(), sitemap.clone()));
+        }
+        if let Some(host) = &self.host {
+            bot.apply(("host".to_owned(), host.clone()));
+        }
+        for clean_param in &self.clean_params {
+            bot.apply((
+                "clean-param".to_owned(),
+                format!("{} {}", clean_param.params.join("&"), clean_param.prefix),
+            ));
+        }
+        bot
+    }
+}
+
+impl From<&str> for RobotsTxt {
+    fn from(value: &str) -> Self {
+        Self::parse(value)
+    }
+}
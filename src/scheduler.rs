@@ -0,0 +1,311 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use url::{Host, Url};
+
+use crate::{CheckError, CheckURL, MetaBot, Permission};
+
+/// The delay between two requests to a host whose `robots.txt` doesn't specify one.
+pub const DEFAULT_DELAY: Duration = Duration::from_secs(1);
+
+/// The longest delay a host is backed off for after it signalled overload.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// A source of the current time, allowing schedulers to be tested without waiting.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// The clock of the operating system.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct HostState {
+    last_access: Option<Instant>,
+    backoff: Duration,
+    blocked_until: Option<Instant>,
+    queue: VecDeque<Url>,
+    /// The number of the latest heap entry of the host. Older entries are outdated and skipped.
+    entry: u64,
+}
+
+/// A host filed under the earliest time it may be accessed, see [`CrawlScheduler::schedule`].
+type Scheduled = Reverse<(Option<Instant>, u64, Host<String>)>;
+
+/// Decides when hosts may be accessed, based on the `robots.txt`s of a [`MetaBot`].
+///
+/// The delay between two requests to a host is taken from its `Crawl-delay` or `Request-rate` directive,
+/// falling back to a default. Hosts answering with status 429 or 503 are backed off exponentially.
+///
+/// Hosts are kept in a heap ordered by the time they may be accessed next, so taking a URL doesn't look at every host.
+/// The state of a host is dropped once no URLs are queued for it and its delay has passed, forgetting any backoff.
+#[derive(Clone, Debug)]
+pub struct CrawlScheduler<C = SystemClock> {
+    meta: MetaBot,
+    clock: C,
+    default_delay: Duration,
+    max_backoff: Duration,
+    hosts: HashMap<Host<String>, HostState>,
+    queued: usize,
+    /// The hosts with queued URLs.
+    ready: BinaryHeap<Scheduled>,
+    /// The hosts without queued URLs, until their delay has passed.
+    idle: BinaryHeap<Scheduled>,
+    entries: u64,
+    /// Whether delays may have changed for every host, so all of them need to be filed again.
+    stale: bool,
+}
+
+impl CrawlScheduler<SystemClock> {
+    /// Creates a new [`CrawlScheduler`] using the clock of the operating system.
+    pub fn new(meta: MetaBot) -> Self {
+        Self::with_clock(meta, SystemClock)
+    }
+}
+
+impl<C: Clock> CrawlScheduler<C> {
+    /// Creates a new [`CrawlScheduler`] using a custom clock.
+    pub fn with_clock(meta: MetaBot, clock: C) -> Self {
+        Self {
+            meta,
+            clock,
+            default_delay: DEFAULT_DELAY,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            hosts: HashMap::new(),
+            queued: 0,
+            ready: BinaryHeap::new(),
+            idle: BinaryHeap::new(),
+            entries: 0,
+            stale: false,
+        }
+    }
+
+    /// Sets the delay used for hosts whose `robots.txt` doesn't specify one.
+    pub fn set_default_delay(&mut self, delay: Duration) {
+        self.default_delay = delay;
+        self.stale = true;
+    }
+
+    /// Sets the longest delay a host is backed off for.
+    pub fn set_max_backoff(&mut self, max_backoff: Duration) {
+        self.max_backoff = max_backoff;
+    }
+
+    /// Gives access to the underlying [`MetaBot`].
+    pub fn meta(&self) -> &MetaBot {
+        &self.meta
+    }
+
+    /// Gives mutable access to the underlying [`MetaBot`], e.g. to add `robots.txt`s.
+    pub fn meta_mut(&mut self) -> &mut MetaBot {
+        self.stale = true;
+        &mut self.meta
+    }
+
+    /// Returns the delay between two requests to a host, including any backoff.
+    pub fn delay(&self, host: &Host<String>) -> Duration {
+        let robots = self.meta.get(host).and_then(|bot| {
+            let rate = bot.request_rate().map(|rate| rate.interval());
+            match (bot.crawl_delay(), rate) {
+                (Some(delay), Some(rate)) => Some(delay.max(rate)),
+                (delay, rate) => delay.or(rate),
+            }
+        });
+        let backoff = self
+            .hosts
+            .get(host)
+            .map_or(Duration::ZERO, |state| state.backoff);
+        robots.unwrap_or(self.default_delay).max(backoff)
+    }
+
+    /// Returns the earliest time at which a host may be accessed again.
+    pub fn next_allowed(&self, host: &Host<String>) -> Instant {
+        let now = self.clock.now();
+        self.earliest(host)
+            .map_or(now, |earliest| earliest.max(now))
+    }
+
+    /// Returns the earliest time at which a host may be accessed, which may lie in the past.
+    /// Returns `None` if nothing restricts access to the host.
+    fn earliest(&self, host: &Host<String>) -> Option<Instant> {
+        let state = self.hosts.get(host)?;
+        let after_delay = state
+            .last_access
+            .map(|last| saturating_add(last, self.delay(host)));
+        after_delay.max(state.blocked_until)
+    }
+
+    /// Checks whether a host may be accessed now.
+    pub fn is_ready(&self, host: &Host<String>) -> bool {
+        self.next_allowed(host) <= self.clock.now()
+    }
+
+    /// Records that a host was accessed now.
+    pub fn record_access(&mut self, host: &Host<String>) {
+        let now = self.clock.now();
+        self.hosts.entry(host.clone()).or_default().last_access = Some(now);
+        self.schedule(host);
+    }
+
+    /// Records the HTTP status a host answered with, adapting its delay.
+    /// Status 429 and 503 double the delay of the host up to the configured maximum; any other status resets it.
+    /// `retry_after` is the value of the `Retry-After` header, if any.
+    pub fn record_response(
+        &mut self,
+        host: &Host<String>,
+        status: u16,
+        retry_after: Option<Duration>,
+    ) {
+        let now = self.clock.now();
+        let delay = self.delay(host);
+        let max_backoff = self.max_backoff;
+        let state = self.hosts.entry(host.clone()).or_default();
+        if status == 429 || status == 503 {
+            state.backoff = delay
+                .saturating_mul(2)
+                .max(Duration::from_secs(1))
+                .min(max_backoff);
+            state.blocked_until =
+                retry_after.map(|retry| saturating_add(now, retry.min(max_backoff)));
+        } else {
+            state.backoff = Duration::ZERO;
+            state.blocked_until = None;
+        }
+        self.schedule(host);
+    }
+
+    /// Queues a URL for crawling.
+    /// URLs denied by their `robots.txt` are not queued.
+    /// # Errors
+    /// Will return `Err` if URL doesn't contain host.
+    pub fn push(&mut self, url: Url) -> Result<Permission, CheckError> {
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        let permission = self.meta.check(url.clone())?;
+        if permission != Permission::Denied {
+            let queue = &mut self.hosts.entry(host.clone()).or_default().queue;
+            queue.push_back(url);
+            self.queued += 1;
+            if queue.len() == 1 {
+                self.schedule(&host);
+            }
+        }
+        Ok(permission)
+    }
+
+    /// Takes a queued URL whose host may be accessed now, recording the access.
+    /// Hosts which have been waiting the longest are served first.
+    pub fn pop_ready(&mut self) -> Option<Url> {
+        let now = self.clock.now();
+        self.settle(now);
+        let Reverse((earliest, _, _)) = self.ready.peek()?;
+        if earliest.is_some_and(|earliest| earliest > now) {
+            return None;
+        }
+        let Reverse((_, _, host)) = self.ready.pop()?;
+
+        let state = self.hosts.get_mut(&host)?;
+        let url = state.queue.pop_front()?;
+        state.last_access = Some(now);
+        self.queued -= 1;
+        self.schedule(&host);
+        Some(url)
+    }
+
+    /// Returns the earliest time at which a queued URL may be crawled.
+    pub fn next_ready_at(&mut self) -> Option<Instant> {
+        let now = self.clock.now();
+        self.settle(now);
+        let Reverse((earliest, _, _)) = self.ready.peek()?;
+        Some(earliest.map_or(now, |earliest| earliest.max(now)))
+    }
+
+    /// Returns the number of hosts whose state is kept: those with queued URLs or whose delay hasn't passed yet.
+    pub fn tracked_hosts(&self) -> usize {
+        self.hosts.len()
+    }
+
+    /// Files a host under the earliest time it may be accessed, replacing its previous entry.
+    fn schedule(&mut self, host: &Host<String>) {
+        let earliest = self.earliest(host);
+        let Some(state) = self.hosts.get_mut(host) else {
+            return;
+        };
+        self.entries += 1;
+        state.entry = self.entries;
+        let scheduled = Reverse((earliest, self.entries, host.clone()));
+        if state.queue.is_empty() {
+            self.idle.push(scheduled);
+        } else {
+            self.ready.push(scheduled);
+        }
+    }
+
+    /// Drops outdated entries from the top of the heaps and the state of idle hosts whose delay has passed.
+    fn settle(&mut self, now: Instant) {
+        if std::mem::take(&mut self.stale) {
+            self.ready.clear();
+            self.idle.clear();
+            let hosts: Vec<_> = self.hosts.keys().cloned().collect();
+            for host in &hosts {
+                self.schedule(host);
+            }
+        }
+        while let Some(Reverse((_, entry, host))) = self.ready.peek() {
+            if self
+                .hosts
+                .get(host)
+                .is_some_and(|state| state.entry == *entry)
+            {
+                break;
+            }
+            self.ready.pop();
+        }
+        while let Some(Reverse((earliest, entry, host))) = self.idle.peek() {
+            let current = self
+                .hosts
+                .get(host)
+                .is_some_and(|state| state.entry == *entry);
+            if current && earliest.is_some_and(|earliest| earliest > now) {
+                break;
+            }
+            let Some(Reverse((_, _, host))) = self.idle.pop() else {
+                break;
+            };
+            if current {
+                self.hosts.remove(&host);
+            }
+        }
+    }
+
+    /// Returns the number of queued URLs.
+    pub fn len(&self) -> usize {
+        self.queued
+    }
+
+    /// Returns `true` if no URLs are queued.
+    pub fn is_empty(&self) -> bool {
+        self.queued == 0
+    }
+}
+
+/// Adds a duration to an instant. If the sum can't be represented, a far future instant is returned instead,
+/// so hosts with huge delays stay blocked.
+fn saturating_add(instant: Instant, mut duration: Duration) -> Instant {
+    loop {
+        if let Some(later) = instant.checked_add(duration) {
+            return later;
+        }
+        duration /= 2;
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     ops::AddAssign,
     sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
 };
 
 #[cfg(feature = "async")]
@@ -10,39 +11,54 @@ use futures_lite::stream::{Stream, StreamExt};
 #[cfg(feature = "percent-decoding")]
 use percent_encoding::percent_decode_str;
 
-use crate::{Permission, PrefixTrie};
+use crate::{
+    AgentBot, CleanParam, ParseProfile, Permission, PrefixTrie, Recogniser, UserAgent,
+    cleanparam::clean_path, trie::witnesses,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 enum Match {
     Star,
     Yes,
     No,
+    Global,
+}
+
+impl Match {
+    /// Combines the matches of consecutive `User-agent` lines, which share their group.
+    fn strongest(self, other: Match) -> Match {
+        match (self, other) {
+            (Match::Yes, _) | (_, Match::Yes) => Match::Yes,
+            (Match::Star, _) | (_, Match::Star) => Match::Star,
+            _ => Match::No,
+        }
+    }
 }
 
 impl AddAssign<String> for SingleBot {
     fn add_assign(&mut self, rhs: String) {
-        let captures = Self::capture_str(rhs.as_str());
+        let captures = Self::capture_str(rhs.as_str(), self.recogniser);
         self.extend_prefixes(captures);
     }
 }
 
 impl AddAssign<&String> for SingleBot {
     fn add_assign(&mut self, rhs: &String) {
-        let captures = Self::capture_str(rhs.as_str());
+        let captures = Self::capture_str(rhs.as_str(), self.recogniser);
         self.extend_prefixes(captures);
     }
 }
 
 impl AddAssign<&str> for SingleBot {
     fn add_assign(&mut self, rhs: &str) {
-        let captures = Self::capture_str(rhs);
+        let captures = Self::capture_str(rhs, self.recogniser);
         self.extend_prefixes(captures);
     }
 }
 
 impl AddAssign<std::fs::File> for SingleBot {
     fn add_assign(&mut self, rhs: std::fs::File) {
-        let captures = Self::capture_file(rhs);
+        let captures = Self::capture_file(rhs, self.recogniser);
         self.extend_prefixes(captures);
     }
 }
@@ -52,7 +68,7 @@ where
     T: std::io::Read,
 {
     fn add_assign(&mut self, rhs: std::io::BufReader<T>) {
-        let captures = Self::capture_reader(rhs);
+        let captures = Self::capture_reader(rhs, self.recogniser);
         self.extend_prefixes(captures);
     }
 }
@@ -88,7 +104,7 @@ where
     where
         T: 'async_trait,
     {
-        let captures = Self::capture_reader_async(rhs);
+        let captures = Self::capture_reader_async(rhs, self.recogniser);
         self.extend_prefixes_async(captures).await;
     }
 }
@@ -97,7 +113,7 @@ where
 #[async_trait]
 impl AddAssignAsync<tokio::fs::File> for SingleBot {
     async fn add_async(&mut self, rhs: tokio::fs::File) {
-        let captures = Self::capture_file_tokio(rhs);
+        let captures = Self::capture_file_tokio(rhs, self.recogniser);
         self.extend_prefixes_async(captures).await;
     }
 }
@@ -106,7 +122,7 @@ impl AddAssignAsync<tokio::fs::File> for SingleBot {
 #[async_trait]
 impl AddAssignAsync<async_std::fs::File> for SingleBot {
     async fn add_async(&mut self, rhs: async_std::fs::File) {
-        let captures = Self::capture_file_asyncstd(rhs);
+        let captures = Self::capture_file_asyncstd(rhs, self.recogniser);
         self.extend_prefixes_async(captures).await;
     }
 }
@@ -115,7 +131,7 @@ impl AddAssignAsync<async_std::fs::File> for SingleBot {
 #[async_trait]
 impl AddAssignAsync<smol::fs::File> for SingleBot {
     async fn add_async(&mut self, rhs: smol::fs::File) {
-        let captures = Self::capture_file_smol(rhs);
+        let captures = Self::capture_file_smol(rhs, self.recogniser);
         self.extend_prefixes_async(captures).await;
     }
 }
@@ -126,80 +142,512 @@ impl Default for SingleBot {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 /// Used to check the `robots.txt` of a single Host.
 pub struct SingleBot {
     prefixes: PrefixTrie<Permission>,
     user_agent: Option<String>,
+    crawl_delay: Option<Duration>,
+    request_rate: Option<RequestRate>,
+    content_signals: Option<ContentSignals>,
+    sitemaps: Vec<String>,
+    host: Option<String>,
+    clean_params: Vec<CleanParam>,
+    profile: ParseProfile,
+    recogniser: Recogniser,
+}
+
+/// The rate at which documents may be requested, as specified by a `Request-rate` directive.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct RequestRate {
+    /// The number of documents which may be requested per period.
+    pub requests: u32,
+    /// The length of a period.
+    pub per: Duration,
+}
+
+impl RequestRate {
+    /// Parses a `Request-rate` value like `1/5` or `10/1m`. Periods without unit are in seconds.
+    pub fn parse(val: &str) -> Option<Self> {
+        let (requests, per) = val.split_once('/')?;
+        let requests: u32 = requests.trim().parse().ok()?;
+        let per = per.trim();
+        let (amount, unit) = match per.find(|c: char| !c.is_ascii_digit()) {
+            Some(idx) => per.split_at(idx),
+            None => (per, "s"),
+        };
+        let amount: u64 = amount.parse().ok()?;
+        let factor = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => return None,
+        };
+        if requests == 0 {
+            return None;
+        }
+        Some(Self {
+            requests,
+            per: Duration::from_secs(amount.checked_mul(factor)?),
+        })
+    }
+
+    /// The time which should pass between two requests.
+    pub fn interval(&self) -> Duration {
+        self.per / self.requests
+    }
+}
+
+/// How content may be used once crawled, as specified by a `Content-Signal` directive like `search=yes, ai-train=no`.
+/// A use which isn't mentioned is `None`, meaning no preference is expressed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
+pub struct ContentSignals {
+    /// Whether the content may be used to build a search index and show links and snippets (`search`).
+    pub search: Option<bool>,
+    /// Whether the content may be used as input to AI models, e.g. for retrieval augmented generation (`ai-input`).
+    pub ai_input: Option<bool>,
+    /// Whether the content may be used to train or fine-tune AI models (`ai-train`).
+    pub ai_train: Option<bool>,
+}
+
+impl ContentSignals {
+    /// Parses a `Content-Signal` value like `search=yes, ai-train=no`. Unknown uses are ignored.
+    /// Returns `None` if no known use is mentioned.
+    pub fn parse(val: &str) -> Option<Self> {
+        let mut signals = Self::default();
+        for signal in val.split(',') {
+            let Some((name, value)) = signal.split_once('=') else {
+                continue;
+            };
+            let value = match value.trim().to_ascii_lowercase().as_str() {
+                "yes" => true,
+                "no" => false,
+                _ => continue,
+            };
+            match name.trim().to_ascii_lowercase().as_str() {
+                "search" => signals.search = Some(value),
+                "ai-input" => signals.ai_input = Some(value),
+                "ai-train" => signals.ai_train = Some(value),
+                _ => {}
+            }
+        }
+        (signals != Self::default()).then_some(signals)
+    }
+
+    /// Adds the preferences of another directive for uses not mentioned yet.
+    pub fn merge(&mut self, other: &ContentSignals) {
+        self.search = self.search.or(other.search);
+        self.ai_input = self.ai_input.or(other.ai_input);
+        self.ai_train = self.ai_train.or(other.ai_train);
+    }
+}
+
+impl std::fmt::Display for ContentSignals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let signals = [
+            ("search", self.search),
+            ("ai-input", self.ai_input),
+            ("ai-train", self.ai_train),
+        ];
+        let mut first = true;
+        for (name, value) in signals {
+            let Some(value) = value else {
+                continue;
+            };
+            if !first {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}={}", if value { "yes" } else { "no" })?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Merges the preferences of a `Content-Signal` value into `signals`.
+pub(crate) fn merge_content_signals(signals: &mut Option<ContentSignals>, val: &str) {
+    if let Some(parsed) = ContentSignals::parse(val) {
+        signals.get_or_insert_default().merge(&parsed);
+    }
+}
+
+/// The permission for a path along with the rule which decided it.
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub struct Verdict {
+    /// The permission for the path.
+    pub permission: Permission,
+    /// The percent-decoded path pattern of the deciding rule, e.g. `/private/*.pdf$`.
+    /// `None` if no rule matched the path.
+    pub rule: Option<String>,
 }
 
 impl SingleBot {
     /// Creates a new [`SingleBot`].
     /// [`SingleBot`] is used to check a single Host. If checking multiple hosts, use [`crate::MetaBot`]
     pub fn new(user_agent: Option<String>) -> Self {
+        SingleBot::with_profile(user_agent, ParseProfile::default())
+    }
+
+    /// Creates a new [`SingleBot`] which interprets `robots.txt`s like the crawlers of a [`ParseProfile`].
+    pub fn with_profile(user_agent: Option<String>, profile: ParseProfile) -> Self {
         let mut prefixes = PrefixTrie::new();
         prefixes.insert("", Permission::Unspecified);
         SingleBot {
             prefixes,
             user_agent,
+            crawl_delay: None,
+            request_rate: None,
+            content_signals: None,
+            sitemaps: Vec::new(),
+            host: None,
+            clean_params: Vec::new(),
+            profile,
+            recogniser: profile.recogniser(),
         }
     }
 
+    /// Returns the profile the bot interprets `robots.txt`s with.
+    pub fn profile(&self) -> ParseProfile {
+        self.profile
+    }
+
+    /// Sets how the directives of newly added `robots.txt`s are recognised.
+    /// Defaults to [`ParseProfile::recogniser`].
+    pub fn set_recogniser(&mut self, recogniser: Recogniser) {
+        self.recogniser = recogniser;
+    }
+
+    /// Creates a new [`SingleBot`] for a parsed `User-Agent` header and adds a `robots.txt`.
+    /// The user agent is the first candidate named by the `robots.txt`, see [`UserAgent::candidates`].
+    pub fn from_user_agent(user_agent: &UserAgent, robots_txt: &str) -> Self {
+        let agent = AgentBot::from(robots_txt)
+            .resolve(user_agent)
+            .or_else(|| user_agent.candidates().into_iter().next());
+        let mut bot = SingleBot::new(agent);
+        bot += robots_txt;
+        bot
+    }
+
+    /// Creates a new [`SingleBot`] which denies every path.
+    /// This is how a host has to be treated if its `robots.txt` is unreachable.
+    pub fn deny_all(user_agent: Option<String>) -> Self {
+        let mut bot = SingleBot::new(user_agent);
+        bot.deny_unspecified();
+        bot
+    }
+
+    /// Denies every path which isn't covered by a rule.
+    pub(crate) fn deny_unspecified(&mut self) {
+        self.prefixes.insert("", Permission::Denied);
+    }
+
     /// Shrinks the internal data structure, saving a few bytes.
     pub fn shrink(&mut self) {
         self.prefixes.shrink();
+        self.sitemaps.shrink_to_fit();
+        self.clean_params.shrink_to_fit();
+    }
+
+    /// Estimates the number of bytes occupied by the bot, including its heap allocations.
+    pub fn approximate_size(&self) -> usize {
+        size_of::<Self>() - size_of::<PrefixTrie<Permission>>()
+            + self.prefixes.approximate_size()
+            + self.user_agent.as_ref().map_or(0, String::capacity)
+            + self.sitemaps.capacity() * size_of::<String>()
+            + self.sitemaps.iter().map(String::capacity).sum::<usize>()
+            + self.host.as_ref().map_or(0, String::capacity)
+            + self.clean_params.capacity() * size_of::<CleanParam>()
+            + self
+                .clean_params
+                .iter()
+                .map(CleanParam::heap_size)
+                .sum::<usize>()
     }
 
     /// Checks the permission defined for a specific URL.
     pub fn check(&self, url: &str) -> Permission {
-        self.prefixes.get(url).unwrap()
+        let permission = self.prefixes.get(url).unwrap();
+        match self.profile.equivalent_path(url) {
+            Some(path) if permission == Permission::Unspecified => {
+                self.prefixes.get(&path).unwrap()
+            }
+            _ => permission,
+        }
     }
 
-    fn decode((op, mut val): (String, String)) -> (String, String) {
-        if val.ends_with('$') {
-            val.pop();
-            val.push('\0');
+    /// Checks the permission defined for a specific URL and tells which rule decided it.
+    pub fn explain(&self, url: &str) -> Verdict {
+        let (mut permission, mut pattern) = self.prefixes.get_match(url).unwrap();
+        if permission == Permission::Unspecified
+            && let Some(path) = self.profile.equivalent_path(url)
+        {
+            (permission, pattern) = self.prefixes.get_match(&path).unwrap();
         }
+        let rule = (!pattern.is_empty()).then(|| pattern.replace('\0', "$"));
+        Verdict { permission, rule }
+    }
+
+    /// Returns a minimised bot giving the same permission to every path.
+    ///
+    /// Rules which never decide a path or whose removal doesn't change any decision are dropped,
+    /// duplicates are merged and sitemaps are sorted. Bots built from reordered or redundant
+    /// `robots.txt`s usually share the same canonical form, so it can be compared with `==`.
+    ///
+    /// Rules to drop are chosen by comparing decisions on example paths derived from the rules. The result is then
+    /// checked on every path like [`SingleBot::equivalent`] does, and if a decision changed, the rules are kept as they are.
+    pub fn canonical(&self) -> SingleBot {
+        let mut entries = self.prefixes.entries();
+        // Dropping a rule may change decisions beyond its own paths, so every candidate is checked
+        // against example paths of all rules.
+        let expected: Vec<(String, Permission)> = entries
+            .iter()
+            .flat_map(|(key, _)| {
+                witnesses(key)
+                    .into_iter()
+                    .chain(witnesses(key.trim_end_matches('*')))
+            })
+            .map(|path| {
+                let permission = self.prefixes.get(&path).unwrap();
+                (path, permission)
+            })
+            .collect();
 
-        #[cfg(not(feature = "percent-decoding"))]
-        return (op, val);
+        // Candidates are tried on a single trie which skips dropped rules, so it is never rebuilt.
+        let mut trie = PrefixTrie::new();
+        for (idx, (key, permission)) in entries.iter().enumerate() {
+            trie.insert(key, (*permission, idx));
+        }
+        let mut alive = vec![true; entries.len()];
+
+        // Trailing wildcards match anything, including nothing, so `/a*` is written as `/a` where that keeps all decisions.
+        // Both match the same paths, so only those can change.
+        for idx in 0..entries.len() {
+            let key = &entries[idx].0;
+            let stripped = key.trim_end_matches('*').to_owned();
+            if stripped.len() == key.len()
+                || entries
+                    .iter()
+                    .zip(&alive)
+                    .any(|((other, _), alive)| *alive && *other == stripped)
+            {
+                continue;
+            }
+            let matched = Self::trie_of(&[(stripped.clone(), Permission::Denied)]);
+            let replacement = entries.len();
+            let permission = entries[idx].1;
+            let previous = trie.insert(&stripped, (permission, replacement));
+            entries.push((stripped.clone(), permission));
+            alive.push(true);
+            alive[idx] = false;
+            let keeps_decisions =
+                expected
+                    .iter()
+                    .filter(|(path, _)| matched.has(path))
+                    .all(|(path, permission)| {
+                        Self::decide(&trie, &alive, path).map(|(decided, _)| decided)
+                            == Some(*permission)
+                    });
+            if !keeps_decisions {
+                alive[idx] = true;
+                alive[replacement] = false;
+                if let Some(previous) = previous {
+                    trie.insert(&stripped, previous);
+                }
+            }
+        }
 
-        #[cfg(feature = "percent-decoding")]
-        if let Ok(decoded) = percent_decode_str(&val).decode_utf8() {
-            (op, decoded.into_owned())
+        // Dropping a rule only changes the decisions it made, so only the paths it decides are looked up again.
+        let mut decided_by: Vec<Vec<usize>> = vec![Vec::new(); entries.len()];
+        for (path_idx, (path, _)) in expected.iter().enumerate() {
+            if let Some((_, idx)) = Self::decide(&trie, &alive, path) {
+                decided_by[idx].push(path_idx);
+            }
+        }
+        // Removing more specific rules first keeps the general ones they repeat.
+        let mut candidates: Vec<usize> = (0..entries.len())
+            .filter(|idx| alive[*idx] && !entries[*idx].0.is_empty())
+            .collect();
+        candidates.sort_by(|a, b| {
+            let (a, b) = (&entries[*a].0, &entries[*b].0);
+            b.len().cmp(&a.len()).then_with(|| a.cmp(b))
+        });
+        for idx in candidates {
+            alive[idx] = false;
+            let moved: Option<Vec<(usize, usize)>> = decided_by[idx]
+                .iter()
+                .map(|&path_idx| {
+                    let (path, permission) = &expected[path_idx];
+                    let (decided, new_idx) = Self::decide(&trie, &alive, path)?;
+                    (decided == *permission).then_some((path_idx, new_idx))
+                })
+                .collect();
+            match moved {
+                Some(moved) => {
+                    decided_by[idx].clear();
+                    for (path_idx, new_idx) in moved {
+                        decided_by[new_idx].push(path_idx);
+                    }
+                }
+                None => alive[idx] = true,
+            }
+        }
+
+        let kept: Vec<_> = entries
+            .into_iter()
+            .zip(alive)
+            .filter_map(|(entry, alive)| alive.then_some(entry))
+            .collect();
+        let mut prefixes = Self::trie_of(&kept);
+        if !prefixes.equivalent(&self.prefixes) {
+            prefixes = self.prefixes.clone();
+        }
+        prefixes.shrink();
+        let mut sitemaps = self.sitemaps.clone();
+        sitemaps.sort();
+
+        SingleBot {
+            prefixes,
+            sitemaps,
+            ..self.clone()
+        }
+    }
+
+    /// Gets the permission for a path and the index of the rule deciding it, skipping the rules which aren't alive.
+    fn decide(
+        trie: &PrefixTrie<(Permission, usize)>,
+        alive: &[bool],
+        path: &str,
+    ) -> Option<(Permission, usize)> {
+        trie.get_by(path, |(permission, idx)| {
+            alive[idx].then_some((permission, idx))
+        })
+    }
+
+    fn trie_of(entries: &[(String, Permission)]) -> PrefixTrie<Permission> {
+        let mut trie = PrefixTrie::new();
+        for (key, value) in entries {
+            trie.insert(key, *value);
+        }
+        trie
+    }
+
+    /// Checks whether two bots give the same permission to every path and specify the same delays, content signals,
+    /// sitemaps, mirror host and `Clean-param` directives.
+    /// The user agents of the bots are not compared.
+    ///
+    /// Permissions are compared exactly, see [`PrefixTrie::equivalent`]. Bots whose profiles disagree on whether
+    /// index files are equivalent to their directory, like [`ParseProfile::Yandex`] and the others, are never equivalent.
+    pub fn equivalent(&self, other: &SingleBot) -> bool {
+        let mut sitemaps = self.sitemaps.clone();
+        let mut other_sitemaps = other.sitemaps.clone();
+        sitemaps.sort();
+        other_sitemaps.sort();
+        let mut clean_params = self.clean_params.clone();
+        let mut other_clean_params = other.clean_params.clone();
+        clean_params.sort();
+        other_clean_params.sort();
+        if self.crawl_delay != other.crawl_delay
+            || self.request_rate != other.request_rate
+            || self.content_signals != other.content_signals
+            || sitemaps != other_sitemaps
+            || self.host != other.host
+            || clean_params != other_clean_params
+            || self.profile.has_equivalent_paths() != other.profile.has_equivalent_paths()
+        {
+            return false;
+        }
+
+        self.prefixes.equivalent(&other.prefixes)
+    }
+
+    /// Returns the delay between two requests, as specified by the `Crawl-delay` directive.
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+
+    /// Returns the rate at which documents may be requested, as specified by the `Request-rate` directive.
+    pub fn request_rate(&self) -> Option<RequestRate> {
+        self.request_rate
+    }
+
+    /// Returns how content may be used, as specified by the `Content-Signal` directives.
+    /// For every use, the first directive mentioning it applies.
+    pub fn content_signals(&self) -> Option<ContentSignals> {
+        self.content_signals
+    }
+
+    /// Returns the locations of the sitemaps listed by `Sitemap` directives, regardless of user agent.
+    pub fn sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+
+    /// Returns the preferred mirror of the site, as specified by the first `Host` directive, regardless of user agent.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Returns the `Clean-param` directives, regardless of user agent.
+    pub fn clean_params(&self) -> &[CleanParam] {
+        &self.clean_params
+    }
+
+    /// Removes the query parameters listed by the `Clean-param` directives covering a path, like `/maps?ll=1&z=2` to `/maps?z=2`.
+    pub fn clean_path(&self, url: &str) -> String {
+        clean_path(&self.clean_params, url)
+    }
+
+    pub(crate) fn decode((op, val): (String, String)) -> (String, String) {
+        if op != "allow" && op != "disallow" {
+            return (op, val);
+        }
+        (op, decode_pattern(val))
+    }
+
+    /// The state of [`SingleBot::conform`] before the first line: how lines apply and whether they follow a `User-agent` line.
+    fn initial_match(profile: ParseProfile) -> (Match, bool) {
+        if profile.keeps_leading_rules() {
+            (Match::Star, false)
         } else {
-            (op, val)
+            (Match::No, false)
         }
     }
 
     fn conform(
-        state: &mut Match,
+        state: &mut (Match, bool),
         precise: &AtomicBool,
         user_agent: Option<&String>,
+        profile: ParseProfile,
         (op, val): (String, String),
     ) -> Option<Option<(Match, (String, String))>> {
+        let after_user_agent = std::mem::replace(&mut state.1, op == "user-agent");
         match op.as_str() {
             "user-agent" => {
-                if val == "*" {
-                    *state = Match::Star;
+                let matched = if val == "*" {
+                    Match::Star
                 } else if let Some(ua) = user_agent
-                    && val.to_lowercase().contains(ua)
+                    && profile.names(&val, ua)
                 {
-                    *state = Match::Yes;
                     precise.store(true, Ordering::Relaxed);
+                    Match::Yes
                 } else {
-                    *state = Match::No;
-                }
+                    Match::No
+                };
+                state.0 = if after_user_agent && profile.merges_user_agents() {
+                    state.0.strongest(matched)
+                } else {
+                    matched
+                };
                 Some(None)
             }
-            "allow" | "disallow" => {
-                if matches!(state, Match::No) {
+            _ if is_global(&op) => Some(Some((Match::Global, (op, val)))),
+            _ => {
+                if matches!(state.0, Match::No) {
                     Some(None)
                 } else {
-                    Some(Some((*state, (op, val))))
+                    Some(Some((state.0, (op, val))))
                 }
             }
-            _ => Some(None),
         }
     }
 
@@ -208,18 +656,71 @@ impl SingleBot {
         precise: bool,
     ) -> Option<(String, String)> {
         match result {
-            Some((Match::Yes, group)) => Some(group),
+            Some((Match::Yes | Match::Global, group)) => Some(group),
             Some((Match::Star, group)) if !precise => Some(group),
             _ => None,
         }
     }
 
-    fn extend_prefix_trie(prefixes: &mut PrefixTrie<Permission>, (op, val): (String, String)) {
+    fn within_star_group(
+        state: &mut (bool, bool),
+        profile: ParseProfile,
+        (op, val): (String, String),
+    ) -> Option<Option<(String, String)>> {
+        let after_user_agent = std::mem::replace(&mut state.1, op == "user-agent");
+        match op.as_str() {
+            "user-agent" => {
+                state.0 =
+                    val == "*" || (after_user_agent && profile.merges_user_agents() && state.0);
+                Some(None)
+            }
+            _ if is_global(&op) => Some(Some((op, val))),
+            _ if state.0 => Some(Some((op, val))),
+            _ => Some(None),
+        }
+    }
+
+    pub(crate) fn apply(&mut self, (op, val): (String, String)) {
         match op.as_str() {
-            "allow" => prefixes.insert(&val, Permission::Allowed),
-            "disallow" => prefixes.insert(&val, Permission::Denied),
+            "allow" => {
+                self.prefixes.insert(&val, Permission::Allowed);
+            }
+            "disallow" => {
+                let prev = self.prefixes.insert(&val, Permission::Denied);
+                if prev == Some(Permission::Allowed) && self.profile.prefers_allow() {
+                    self.prefixes.insert(&val, Permission::Allowed);
+                }
+            }
+            "crawl-delay" => {
+                if self.crawl_delay.is_none() {
+                    self.crawl_delay = parse_crawl_delay(&val);
+                }
+            }
+            "request-rate" => {
+                if self.request_rate.is_none() {
+                    self.request_rate = RequestRate::parse(&val);
+                }
+            }
+            "content-signal" => merge_content_signals(&mut self.content_signals, &val),
+            "sitemap" => {
+                if !self.sitemaps.contains(&val) {
+                    self.sitemaps.push(val);
+                }
+            }
+            "host" => {
+                if self.host.is_none() && !val.is_empty() {
+                    self.host = Some(val);
+                }
+            }
+            "clean-param" => {
+                if let Some(clean_param) = CleanParam::parse(&val)
+                    && !self.clean_params.contains(&clean_param)
+                {
+                    self.clean_params.push(clean_param);
+                }
+            }
             _ => unreachable!(),
-        };
+        }
     }
 
     #[cfg(feature = "async")]
@@ -232,51 +733,48 @@ impl SingleBot {
         }
     }
 
+    #[cfg(feature = "async")]
     async fn extend_prefixes_without_user_agent_async(
         &mut self,
         captures: impl Stream<Item = (String, String)>,
     ) {
+        let profile = self.profile;
         captures
+            .filter_map(|pair| profile.prepare(pair))
             .map(Self::decode)
-            .scan(false, |state, (op, val)| match op.as_str() {
-                "user-agent" => {
-                    *state = val == "*";
-                    Some(None)
-                }
-                "allow" if *state => Some(Some((true, val))),
-                "disallow" if *state => Some(Some((false, val))),
-                _ => Some(None),
+            .scan((profile.keeps_leading_rules(), false), |state, pair| {
+                Self::within_star_group(state, profile, pair)
             })
             .filter_map(|opt| opt)
-            .for_each(|(allow, val): (bool, String)| {
-                self.prefixes.insert(
-                    &val,
-                    if allow {
-                        Permission::Allowed
-                    } else {
-                        Permission::Denied
-                    },
-                );
-            })
+            .for_each(|pair| self.apply(pair))
             .await;
     }
 
+    #[cfg(feature = "async")]
     async fn extend_prefixes_with_user_agent_async(
         &mut self,
         captures: impl Stream<Item = (String, String)>,
     ) {
         let precise = AtomicBool::new(false);
+        let profile = self.profile;
 
         captures
+            .filter_map(|pair| profile.prepare(pair))
             .map(Self::decode)
-            .scan(Match::No, |state, (op, val)| {
-                Self::conform(state, &precise, self.user_agent.as_ref(), (op, val))
+            .scan(Self::initial_match(profile), |state, (op, val)| {
+                Self::conform(
+                    state,
+                    &precise,
+                    self.user_agent.as_ref(),
+                    profile,
+                    (op, val),
+                )
             })
             .collect::<Vec<_>>()
             .await
             .into_iter()
             .filter_map(|result| Self::filter_weak(result, precise.load(Ordering::Relaxed)))
-            .for_each(|pair| Self::extend_prefix_trie(&mut self.prefixes, pair));
+            .for_each(|pair| self.apply(pair));
     }
 
     fn extend_prefixes(&mut self, captures: impl Iterator<Item = (String, String)>) {
@@ -291,28 +789,15 @@ impl SingleBot {
         &mut self,
         captures: impl Iterator<Item = (String, String)>,
     ) {
+        let profile = self.profile;
         captures
+            .filter_map(|pair| profile.prepare(pair))
             .map(Self::decode)
-            .scan(false, |state, (op, val)| match op.as_str() {
-                "user-agent" => {
-                    *state = val == "*";
-                    Some(None)
-                }
-                "allow" if *state => Some(Some((true, val))),
-                "disallow" if *state => Some(Some((false, val))),
-                _ => Some(None),
+            .scan((profile.keeps_leading_rules(), false), |state, pair| {
+                Self::within_star_group(state, profile, pair)
             })
             .flatten()
-            .for_each(|(allow, val): (bool, String)| {
-                self.prefixes.insert(
-                    &val,
-                    if allow {
-                        Permission::Allowed
-                    } else {
-                        Permission::Denied
-                    },
-                );
-            });
+            .for_each(|pair| self.apply(pair));
     }
 
     fn extend_prefixes_with_user_agent(
@@ -320,111 +805,129 @@ impl SingleBot {
         captures: impl Iterator<Item = (String, String)>,
     ) {
         let precise = AtomicBool::new(false);
+        let profile = self.profile;
 
         captures
+            .filter_map(|pair| profile.prepare(pair))
             .map(Self::decode)
-            .scan(Match::No, |state, (op, val)| {
-                Self::conform(state, &precise, self.user_agent.as_ref(), (op, val))
+            .scan(Self::initial_match(profile), |state, (op, val)| {
+                Self::conform(
+                    state,
+                    &precise,
+                    self.user_agent.as_ref(),
+                    profile,
+                    (op, val),
+                )
             })
             .collect::<Vec<_>>()
             .into_iter()
             .filter_map(|result| Self::filter_weak(result, precise.load(Ordering::Relaxed)))
-            .for_each(|pair| Self::extend_prefix_trie(&mut self.prefixes, pair));
+            .for_each(|pair| self.apply(pair));
     }
 
     #[cfg(feature = "async-smol")]
-    fn capture_file_smol(file: smol::fs::File) -> impl Stream<Item = (String, String)> {
+    pub(crate) fn capture_file_smol(
+        file: smol::fs::File,
+        recogniser: Recogniser,
+    ) -> impl Stream<Item = (String, String)> {
         let reader = smol::io::BufReader::new(file);
-        Self::capture_reader_async(reader)
+        Self::capture_reader_async(reader, recogniser)
     }
 
     #[cfg(feature = "async-async-std")]
-    fn capture_file_asyncstd(file: async_std::fs::File) -> impl Stream<Item = (String, String)> {
+    pub(crate) fn capture_file_asyncstd(
+        file: async_std::fs::File,
+        recogniser: Recogniser,
+    ) -> impl Stream<Item = (String, String)> {
         let reader = async_std::io::BufReader::new(file);
-        Self::capture_reader_async(reader)
+        Self::capture_reader_async(reader, recogniser)
     }
 
     #[cfg(feature = "async-tokio")]
-    fn capture_file_tokio(file: tokio::fs::File) -> impl Stream<Item = (String, String)> {
+    pub(crate) fn capture_file_tokio(
+        file: tokio::fs::File,
+        recogniser: Recogniser,
+    ) -> impl Stream<Item = (String, String)> {
         use tokio_util::compat::TokioAsyncReadCompatExt;
 
         let reader = tokio::io::BufReader::new(file);
         let compat = reader.compat();
-        Self::capture_reader_async(compat)
+        Self::capture_reader_async(compat, recogniser)
     }
 
     #[cfg(feature = "async")]
-    fn capture_reader_async(
+    pub(crate) fn capture_reader_async(
         reader: impl futures_lite::AsyncBufReadExt + Unpin,
+        recogniser: Recogniser,
     ) -> impl Stream<Item = (String, String)> {
         let lines = reader.lines().filter_map(|line| line.ok());
-        Self::capture_lines_async(lines)
+        Self::capture_lines_async(lines, recogniser)
     }
 
     #[cfg(feature = "async")]
     fn capture_lines_async(
         lines: impl Stream<Item = String>,
+        recogniser: Recogniser,
     ) -> impl Stream<Item = (String, String)> {
         lines
-            .map(strip_comment)
-            .filter_map(parse_pair)
-            .filter_map(filter_and_normalize)
+            .filter_map(move |line| recogniser.recognise(strip_comment_str(&line)))
+            .map(|line| (line.directive, line.value))
     }
 
-    fn capture_file(file: std::fs::File) -> impl Iterator<Item = (String, String)> {
+    pub(crate) fn capture_file(
+        file: std::fs::File,
+        recogniser: Recogniser,
+    ) -> impl Iterator<Item = (String, String)> {
         let reader = std::io::BufReader::new(file);
-        Self::capture_reader(reader)
+        Self::capture_reader(reader, recogniser)
     }
 
-    fn capture_reader(reader: impl std::io::BufRead) -> impl Iterator<Item = (String, String)> {
+    pub(crate) fn capture_reader(
+        reader: impl std::io::BufRead,
+        recogniser: Recogniser,
+    ) -> impl Iterator<Item = (String, String)> {
         let lines = reader.lines().map_while(Result::ok);
-        Self::capture_lines(lines)
+        Self::capture_lines(lines, recogniser)
     }
 
-    fn capture_str(input: &str) -> impl Iterator<Item = (String, String)> {
+    pub(crate) fn capture_str(
+        input: &str,
+        recogniser: Recogniser,
+    ) -> impl Iterator<Item = (String, String)> {
         let lines = input.lines();
-        Self::capture_lines_str(lines)
+        Self::capture_lines_str(lines, recogniser)
     }
 
     fn capture_lines(
         lines: impl IntoIterator<Item = String>,
+        recogniser: Recogniser,
     ) -> impl Iterator<Item = (String, String)> {
         lines
             .into_iter()
-            .map(strip_comment)
-            .filter_map(parse_pair)
-            .filter_map(filter_and_normalize)
+            .filter_map(move |line| recogniser.recognise(strip_comment_str(&line)))
+            .map(|line| (line.directive, line.value))
     }
 
     fn capture_lines_str<'a>(
         lines: impl IntoIterator<Item = &'a str>,
+        recogniser: Recogniser,
     ) -> impl Iterator<Item = (String, String)> {
         lines
             .into_iter()
             .map(strip_comment_str)
-            .filter_map(parse_pair_str)
-            .map(|(op, val)| (op.trim().to_lowercase(), val.trim()))
-            .filter(|(_, val)| !val.contains(' '))
-            .filter(|(op, _)| op == "allow" || op == "disallow" || op == "user-agent")
-            .map(|(op, val)| (op, val.to_owned()))
+            .filter_map(move |line| recogniser.recognise(line))
+            .map(|line| (line.directive, line.value))
     }
 }
 
-fn strip_comment_str(line: &str) -> &str {
+pub(crate) fn strip_comment_str(line: &str) -> &str {
     match line.find('#') {
         Some(idx) => &line[..idx],
         None => line,
     }
 }
 
-fn strip_comment(mut line: String) -> String {
-    if let Some(idx) = line.find('#') {
-        line.truncate(idx);
-    }
-    line
-}
-
-fn parse_pair_str(line: &str) -> Option<(&str, &str)> {
+pub(crate) fn parse_pair_str(line: &str) -> Option<(&str, &str)> {
     if line.is_empty() {
         None
     } else {
@@ -432,25 +935,58 @@ fn parse_pair_str(line: &str) -> Option<(&str, &str)> {
     }
 }
 
-fn parse_pair(mut line: String) -> Option<(String, String)> {
-    if line.is_empty() {
-        return None;
+/// Turns the pattern of an `Allow` or `Disallow` rule into a key of the trie.
+pub(crate) fn decode_pattern(mut val: String) -> String {
+    if val.ends_with('$') {
+        val.pop();
+        val.push('\0');
     }
-    let idx = line.find(':')?;
-    let mut val = line.split_off(idx);
-    val.remove(0);
-    let op = line;
-    Some((op, val))
-}
 
-fn filter_and_normalize((op, val): (String, String)) -> Option<(String, String)> {
-    let op_trim = op.trim().to_lowercase();
-    if !(op_trim == "allow" || op_trim == "disallow" || op_trim == "user-agent") {
-        return None;
+    #[cfg(not(feature = "percent-decoding"))]
+    return val;
+
+    #[cfg(feature = "percent-decoding")]
+    if let Ok(decoded) = percent_decode_str(&val).decode_utf8() {
+        decoded.into_owned()
+    } else {
+        val
     }
-    let val_trim = val.trim();
-    if val_trim.contains(' ') {
+}
+
+/// The directives which are kept by the parser, in lowercase.
+const DIRECTIVES: [&str; 9] = [
+    "user-agent",
+    "allow",
+    "disallow",
+    "crawl-delay",
+    "request-rate",
+    "content-signal",
+    "sitemap",
+    "host",
+    "clean-param",
+];
+
+pub(crate) fn is_directive(op: &str) -> bool {
+    DIRECTIVES.contains(&op)
+}
+
+/// Checks whether the value of a directive may contain spaces, like `Content-Signal: search=yes, ai-train=no`.
+pub(crate) fn allows_spaces(op: &str) -> bool {
+    op == "content-signal" || op == "clean-param"
+}
+
+/// Checks whether a directive applies regardless of user agent, like `Sitemap`.
+pub(crate) fn is_global(op: &str) -> bool {
+    matches!(op, "sitemap" | "host" | "clean-param")
+}
+
+/// The longest `Crawl-delay` which is kept, longer delays are shortened to it.
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub(crate) fn parse_crawl_delay(val: &str) -> Option<Duration> {
+    let secs: f64 = val.parse().ok()?;
+    if secs.is_nan() {
         return None;
     }
-    Some((op_trim, val_trim.to_owned()))
+    Duration::try_from_secs_f64(secs.min(MAX_CRAWL_DELAY.as_secs_f64())).ok()
 }
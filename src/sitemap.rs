@@ -0,0 +1,359 @@
+use std::{
+    fmt::Display,
+    io::{BufRead, BufReader},
+};
+
+use flate2::bufread::MultiGzDecoder;
+use quick_xml::{
+    escape::resolve_predefined_entity,
+    events::Event,
+    name::{Namespace, ResolveResult},
+    reader::NsReader,
+};
+
+/// How frequently the page at a location is likely to change, as given by `<changefreq>`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum ChangeFreq {
+    /// The page changes each time it is accessed.
+    Always,
+    /// The page changes hourly.
+    Hourly,
+    /// The page changes daily.
+    Daily,
+    /// The page changes weekly.
+    Weekly,
+    /// The page changes monthly.
+    Monthly,
+    /// The page changes yearly.
+    Yearly,
+    /// The page is archived and doesn't change.
+    Never,
+}
+
+impl ChangeFreq {
+    /// Parses a `<changefreq>` value, ignoring case.
+    pub fn parse(val: &str) -> Option<Self> {
+        let freq = match val.trim().to_lowercase().as_str() {
+            "always" => Self::Always,
+            "hourly" => Self::Hourly,
+            "daily" => Self::Daily,
+            "weekly" => Self::Weekly,
+            "monthly" => Self::Monthly,
+            "yearly" => Self::Yearly,
+            "never" => Self::Never,
+            _ => return None,
+        };
+        Some(freq)
+    }
+}
+
+/// A location listed by a sitemap.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SitemapEntry {
+    /// The URL of the location.
+    pub loc: String,
+    /// When the location was last modified, as given by `<lastmod>` (W3C datetime).
+    pub lastmod: Option<String>,
+    /// How frequently the location is likely to change.
+    pub changefreq: Option<ChangeFreq>,
+    /// The priority of the location relative to other locations on the site, between `0.0` and `1.0`.
+    pub priority: Option<f32>,
+}
+
+/// An item of a sitemap.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SitemapItem {
+    /// A page, listed by a `<url>` of an `<urlset>` or a line of a text sitemap.
+    Url(SitemapEntry),
+    /// Another sitemap, listed by a `<sitemap>` of a `<sitemapindex>`.
+    Sitemap(SitemapEntry),
+}
+
+impl SitemapItem {
+    /// Returns the listed location.
+    pub fn entry(&self) -> &SitemapEntry {
+        match self {
+            SitemapItem::Url(entry) | SitemapItem::Sitemap(entry) => entry,
+        }
+    }
+
+    /// Returns the URL of the listed location.
+    pub fn loc(&self) -> &str {
+        &self.entry().loc
+    }
+}
+
+/// Error if reading a sitemap fails.
+#[derive(Debug)]
+pub enum SitemapError {
+    /// Failed at reading from the source.
+    Io(std::io::Error),
+    /// The sitemap is not well-formed XML.
+    Xml(quick_xml::Error),
+}
+
+impl Display for SitemapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SitemapError::Io(err) => write!(f, "failed to read sitemap: {err}"),
+            SitemapError::Xml(err) => write!(f, "malformed sitemap: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SitemapError {}
+
+/// The namespaces of the sitemap protocol, including the legacy ones of Google, as prefixes.
+const NAMESPACES: [&[u8]; 2] = [
+    b"http://www.sitemaps.org/schemas/sitemap/",
+    b"http://www.google.com/schemas/sitemap/",
+];
+
+/// Checks whether an element belongs to the sitemap protocol rather than an extension like `<image:loc>`.
+/// Elements without namespace are accepted, as many sitemaps omit it.
+fn in_sitemap_namespace(ns: &ResolveResult) -> bool {
+    match ns {
+        ResolveResult::Unbound => true,
+        ResolveResult::Bound(Namespace(ns)) => {
+            NAMESPACES.iter().any(|prefix| ns.starts_with(prefix))
+        }
+        ResolveResult::Unknown(_) => false,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Field {
+    Loc,
+    Lastmod,
+    Changefreq,
+    Priority,
+}
+
+impl Field {
+    fn parse(name: &[u8]) -> Option<Self> {
+        match name {
+            b"loc" => Some(Field::Loc),
+            b"lastmod" => Some(Field::Lastmod),
+            b"changefreq" => Some(Field::Changefreq),
+            b"priority" => Some(Field::Priority),
+            _ => None,
+        }
+    }
+}
+
+struct XmlState<'a> {
+    reader: NsReader<Box<dyn BufRead + 'a>>,
+    buf: Vec<u8>,
+    /// The depth of the current element, the root element being at depth 1.
+    depth: usize,
+    /// The item being read, whether it is a sitemap and its depth.
+    item: Option<(bool, SitemapEntry, usize)>,
+    field: Option<(Field, String)>,
+}
+
+enum Format<'a> {
+    Xml(Box<XmlState<'a>>),
+    Text(std::io::Lines<Box<dyn BufRead + 'a>>),
+    Done,
+}
+
+/// Reads the items of a sitemap one at a time, without loading the whole sitemap into memory.
+///
+/// Supports XML sitemaps (`<urlset>` and `<sitemapindex>`) and text sitemaps (one URL per line),
+/// both optionally compressed with gzip. The format is detected from the content.
+pub struct SitemapReader<'a> {
+    format: Format<'a>,
+}
+
+impl<'a> SitemapReader<'a> {
+    /// Creates a new [`SitemapReader`] reading from `reader`.
+    /// # Errors
+    /// Will return `Err` if detecting the format fails because reading fails.
+    pub fn new(reader: impl BufRead + 'a) -> Result<Self, SitemapError> {
+        let mut reader: Box<dyn BufRead + 'a> = Box::new(reader);
+        if reader
+            .fill_buf()
+            .map_err(SitemapError::Io)?
+            .starts_with(&[0x1f, 0x8b])
+        {
+            reader = Box::new(BufReader::new(MultiGzDecoder::new(reader)));
+        }
+        let buffered = reader.fill_buf().map_err(SitemapError::Io)?;
+        let is_xml = buffered
+            .iter()
+            // Skip a byte order mark as well as leading whitespace.
+            .find(|byte| !byte.is_ascii_whitespace() && !matches!(byte, 0xef | 0xbb | 0xbf))
+            .is_some_and(|byte| *byte == b'<');
+
+        let format = if is_xml {
+            let mut reader = NsReader::from_reader(reader);
+            reader.config_mut().trim_text(true);
+            Format::Xml(Box::new(XmlState {
+                reader,
+                buf: Vec::new(),
+                depth: 0,
+                item: None,
+                field: None,
+            }))
+        } else {
+            Format::Text(reader.lines())
+        };
+        Ok(Self { format })
+    }
+
+    fn next_text(
+        lines: &mut std::io::Lines<Box<dyn BufRead + 'a>>,
+    ) -> Option<Result<SitemapItem, SitemapError>> {
+        for line in lines {
+            match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => {
+                    return Some(Ok(SitemapItem::Url(SitemapEntry {
+                        loc: line.trim().to_owned(),
+                        ..Default::default()
+                    })));
+                }
+                Err(err) => return Some(Err(SitemapError::Io(err))),
+            }
+        }
+        None
+    }
+
+    fn next_xml(&mut self) -> Option<Result<SitemapItem, SitemapError>> {
+        let Format::Xml(state) = &mut self.format else {
+            return None;
+        };
+        let XmlState {
+            reader,
+            buf,
+            depth,
+            item,
+            field,
+        } = &mut **state;
+        loop {
+            buf.clear();
+            let (ns, event) = match reader.read_resolved_event_into(buf) {
+                Ok(resolved) => resolved,
+                Err(err) => return Some(Err(SitemapError::Xml(err))),
+            };
+            match event {
+                Event::Start(start) => {
+                    *depth += 1;
+                    if !in_sitemap_namespace(&ns) {
+                        continue;
+                    }
+                    match start.local_name().as_ref() {
+                        b"url" => *item = Some((false, SitemapEntry::default(), *depth)),
+                        b"sitemap" => *item = Some((true, SitemapEntry::default(), *depth)),
+                        // Only direct children of an item describe it.
+                        name if item
+                            .as_ref()
+                            .is_some_and(|(_, _, item_depth)| *item_depth + 1 == *depth) =>
+                        {
+                            *field = Field::parse(name).map(|name| (name, String::new()));
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some((_, content)) = field
+                        && let Ok(text) = text.xml10_content()
+                    {
+                        content.push_str(&text);
+                    }
+                }
+                Event::CData(cdata) => {
+                    if let Some((_, content)) = field
+                        && let Ok(text) = cdata.decode()
+                    {
+                        content.push_str(&text);
+                    }
+                }
+                Event::GeneralRef(reference) => {
+                    if let Some((_, content)) = field {
+                        if let Ok(Some(ch)) = reference.resolve_char_ref() {
+                            content.push(ch);
+                        } else if let Ok(name) = reference.decode()
+                            && let Some(resolved) = resolve_predefined_entity(&name)
+                        {
+                            content.push_str(resolved);
+                        }
+                    }
+                }
+                Event::End(end) => {
+                    let end_depth = *depth;
+                    *depth = depth.saturating_sub(1);
+                    if !in_sitemap_namespace(&ns) {
+                        continue;
+                    }
+                    let item_depth = item.as_ref().map(|(_, _, item_depth)| *item_depth);
+                    match end.local_name().as_ref() {
+                        b"url" | b"sitemap" if item_depth == Some(end_depth) => {
+                            if let Some((is_index, entry, _)) = item.take()
+                                && !entry.loc.is_empty()
+                            {
+                                return Some(Ok(if is_index {
+                                    SitemapItem::Sitemap(entry)
+                                } else {
+                                    SitemapItem::Url(entry)
+                                }));
+                            }
+                        }
+                        name if Field::parse(name).is_some()
+                            && item_depth.is_some_and(|item_depth| item_depth + 1 == end_depth) =>
+                        {
+                            if let (Some((_, entry, _)), Some((name, content))) =
+                                (item.as_mut(), field.take())
+                            {
+                                let content = content.trim();
+                                match name {
+                                    Field::Loc => entry.loc = content.to_owned(),
+                                    Field::Lastmod => entry.lastmod = Some(content.to_owned()),
+                                    Field::Changefreq => {
+                                        entry.changefreq = ChangeFreq::parse(content)
+                                    }
+                                    Field::Priority => entry.priority = content.parse().ok(),
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => return None,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a str> for SitemapReader<'a> {
+    fn from(value: &'a str) -> Self {
+        // Reading from a string can't fail.
+        Self::new(value.as_bytes()).unwrap()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SitemapReader<'a> {
+    type Error = SitemapError;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Iterator for SitemapReader<'_> {
+    type Item = Result<SitemapItem, SitemapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match &mut self.format {
+            Format::Xml(_) => self.next_xml(),
+            Format::Text(lines) => Self::next_text(lines),
+            Format::Done => None,
+        };
+        // Errors are not recoverable, so reading stops after the first one.
+        if !matches!(next, Some(Ok(_))) {
+            self.format = Format::Done;
+        }
+        next
+    }
+}
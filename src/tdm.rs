@@ -0,0 +1,255 @@
+use std::{collections::HashMap, fmt::Display, ops::AddAssign};
+
+use url::{Host, Url};
+
+use crate::{
+    CheckError, CheckURL, Permission, PrefixTrie, SingleBot, html::tags, singlebot::decode_pattern,
+};
+
+/// The text-and-data-mining policy of a resource, as declared by the TDM Reservation Protocol (TDMRep).
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub struct TdmPolicy {
+    /// Whether the rights holder reserves text and data mining rights (`tdm-reservation: 1`).
+    pub reserved: bool,
+    /// The location of a policy describing how mining rights may be licensed (`tdm-policy`).
+    pub policy: Option<String>,
+}
+
+impl TdmPolicy {
+    /// Reads a policy from the values of the `tdm-reservation` and `tdm-policy` HTTP headers.
+    /// Returns `None` if the reservation is missing or invalid.
+    pub fn from_headers(reservation: Option<&str>, policy: Option<&str>) -> Option<Self> {
+        let reserved = match reservation?.trim() {
+            "1" => true,
+            "0" => false,
+            _ => return None,
+        };
+        Some(Self {
+            reserved,
+            policy: policy.map(|policy| policy.trim().to_owned()),
+        })
+    }
+
+    /// Reads a policy from the `tdm-reservation` and `tdm-policy` meta tags of an HTML document.
+    /// Returns `None` if the reservation is missing or invalid.
+    pub fn from_html(html: &str) -> Option<Self> {
+        let mut reservation = None;
+        let mut policy = None;
+        for tag in tags(html).filter(|tag| tag.name == "meta") {
+            let (Some(name), Some(content)) = (tag.attribute("name"), tag.attribute("content"))
+            else {
+                continue;
+            };
+            if name.eq_ignore_ascii_case("tdm-reservation") {
+                reservation.get_or_insert_with(|| content.to_owned());
+            } else if name.eq_ignore_ascii_case("tdm-policy") {
+                policy.get_or_insert_with(|| content.to_owned());
+            }
+        }
+        Self::from_headers(reservation.as_deref(), policy.as_deref())
+    }
+}
+
+/// Error if reading a `tdmrep.json` fails.
+#[derive(Debug)]
+pub enum TdmError {
+    /// The file is not valid JSON.
+    Json(serde_json::Error),
+    /// The file is valid JSON, but not a list of rules with `location` and `tdm-reservation`.
+    Format(String),
+}
+
+impl Display for TdmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TdmError::Json(err) => write!(f, "malformed tdmrep.json: {err}"),
+            TdmError::Format(reason) => write!(f, "invalid tdmrep.json: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for TdmError {}
+
+/// The rules of a `/.well-known/tdmrep.json`, assigning a [`TdmPolicy`] to locations of an origin.
+///
+/// Locations are patterns like the rules of a `robots.txt`, so the most specific matching location applies.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TdmRep {
+    locations: PrefixTrie<usize>,
+    policies: Vec<TdmPolicy>,
+}
+
+impl TdmRep {
+    /// Parses a `tdmrep.json`. Rules repeating an earlier location are ignored.
+    /// # Errors
+    /// Will return `Err` if the file isn't valid JSON or doesn't hold a list of rules.
+    pub fn parse(json: &str) -> Result<Self, TdmError> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(TdmError::Json)?;
+        let rules = value
+            .as_array()
+            .ok_or_else(|| TdmError::Format("expected a list of rules".to_owned()))?;
+
+        let mut tdmrep = Self::default();
+        for (idx, rule) in rules.iter().enumerate() {
+            let location = rule
+                .get("location")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| TdmError::Format(format!("rule {idx} has no location")))?;
+            let reserved = match rule.get("tdm-reservation") {
+                Some(serde_json::Value::Number(number)) => number.as_u64(),
+                Some(serde_json::Value::String(number)) => number.trim().parse().ok(),
+                _ => None,
+            };
+            let reserved = match reserved {
+                Some(0) => false,
+                Some(1) => true,
+                _ => {
+                    return Err(TdmError::Format(format!(
+                        "rule {idx} has no valid tdm-reservation"
+                    )));
+                }
+            };
+            let policy = rule
+                .get("tdm-policy")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_owned);
+
+            let key = decode_pattern(location.to_owned());
+            if let Some(prev) = tdmrep.locations.insert(&key, tdmrep.policies.len()) {
+                tdmrep.locations.insert(&key, prev);
+                continue;
+            }
+            tdmrep.policies.push(TdmPolicy { reserved, policy });
+        }
+        Ok(tdmrep)
+    }
+
+    /// Returns the policy of the most specific location matching a path, if any.
+    pub fn policy(&self, path: &str) -> Option<&TdmPolicy> {
+        self.locations.get(path).map(|idx| &self.policies[idx])
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct OriginTdm {
+    tdmrep: Option<TdmRep>,
+    ai_txt: Option<SingleBot>,
+}
+
+/// Used to check whether the content of multiple Hosts may be used for text and data mining, such as training AI models.
+///
+/// Combines the `tdmrep.json` and the `ai.txt` of every host. Checks answer [`Permission::Denied`] if either reserves
+/// the rights for a URL, [`Permission::Allowed`] if either explicitly allows mining it and [`Permission::Unspecified`] otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TdmBot {
+    hosts: HashMap<Host<String>, OriginTdm>,
+    user_agent: Option<String>,
+}
+
+impl TdmBot {
+    /// Creates a new [`TdmBot`]. The user agent selects the groups of `ai.txt`s, like it does for [`SingleBot`].
+    pub fn new(user_agent: Option<String>) -> Self {
+        Self {
+            hosts: HashMap::new(),
+            user_agent,
+        }
+    }
+
+    /// Sets the parsed `/.well-known/tdmrep.json` of a host, replacing the previous one.
+    pub fn set_tdmrep(&mut self, host: Host<String>, tdmrep: TdmRep) {
+        self.hosts.entry(host).or_default().tdmrep = Some(tdmrep);
+    }
+
+    /// Parses and sets the `/.well-known/tdmrep.json` of a host, replacing the previous one.
+    /// # Errors
+    /// Will return `Err` if the file isn't a valid `tdmrep.json`. The previous one is kept in that case.
+    pub fn add_tdmrep(&mut self, host: Host<String>, json: &str) -> Result<(), TdmError> {
+        self.set_tdmrep(host, TdmRep::parse(json)?);
+        Ok(())
+    }
+
+    /// Adds an `ai.txt` for a host. It uses the syntax of a `robots.txt`, with patterns like `*.jpg` denying file types.
+    pub fn add_ai_txt<T>(&mut self, host: Host<String>, ai_txt: T)
+    where
+        SingleBot: AddAssign<T>,
+    {
+        let user_agent = self.user_agent.clone();
+        let bot = self
+            .hosts
+            .entry(host)
+            .or_default()
+            .ai_txt
+            .get_or_insert_with(|| SingleBot::new(user_agent));
+        *bot += ai_txt;
+    }
+
+    /// Forgets the policies of a host.
+    /// Returns `false` if no policy was known for the host.
+    pub fn remove(&mut self, host: &Host<String>) -> bool {
+        self.hosts.remove(host).is_some()
+    }
+
+    /// Returns the `tdmrep.json` policy applying to a URL, if any.
+    pub fn policy(&self, url: &Url) -> Option<&TdmPolicy> {
+        let host = url.host()?.to_owned();
+        self.hosts.get(&host)?.tdmrep.as_ref()?.policy(url.path())
+    }
+
+    fn permission(&self, url: &Url) -> Result<Permission, CheckError> {
+        let host = url.host().ok_or(CheckError::MissingHost)?.to_owned();
+        let Some(origin) = self.hosts.get(&host) else {
+            return Ok(Permission::Unspecified);
+        };
+        let tdmrep = origin
+            .tdmrep
+            .as_ref()
+            .and_then(|tdmrep| tdmrep.policy(url.path()))
+            .map_or(Permission::Unspecified, |policy| {
+                if policy.reserved {
+                    Permission::Denied
+                } else {
+                    Permission::Allowed
+                }
+            });
+        let ai_txt = origin
+            .ai_txt
+            .as_ref()
+            .map_or(Permission::Unspecified, |bot| bot.check(url.path()));
+        Ok(match (tdmrep, ai_txt) {
+            (Permission::Denied, _) | (_, Permission::Denied) => Permission::Denied,
+            (Permission::Allowed, _) | (_, Permission::Allowed) => Permission::Allowed,
+            _ => Permission::Unspecified,
+        })
+    }
+}
+
+impl CheckURL<Url> for TdmBot {
+    fn check(&self, url: Url) -> Result<Permission, CheckError> {
+        self.permission(&url)
+    }
+}
+
+impl CheckURL<&Url> for TdmBot {
+    fn check(&self, url: &Url) -> Result<Permission, CheckError> {
+        self.permission(url)
+    }
+}
+
+impl CheckURL<&str> for TdmBot {
+    fn check(&self, url: &str) -> Result<Permission, CheckError> {
+        let url = Url::parse(url).map_err(CheckError::ParseError)?;
+        self.permission(&url)
+    }
+}
+
+impl CheckURL<&String> for TdmBot {
+    fn check(&self, url: &String) -> Result<Permission, CheckError> {
+        self.check(url.as_str())
+    }
+}
+
+impl CheckURL<String> for TdmBot {
+    fn check(&self, url: String) -> Result<Permission, CheckError> {
+        self.check(url.as_str())
+    }
+}
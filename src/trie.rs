@@ -1,4 +1,8 @@
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
 
 /// Prefix trie that supports Wildcards and Exacts
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -15,6 +19,18 @@ impl<T: Copy> Default for PrefixTrie<T> {
     }
 }
 
+impl<T: Copy + Hash> Hash for PrefixTrie<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.exact.hash(state);
+        self.wildcard.hash(state);
+        // Children are hashed in order, so tries which compare equal hash equally.
+        let mut children: Vec<_> = self.children.iter().collect();
+        children.sort_unstable_by_key(|(letter, _)| **letter);
+        children.hash(state);
+    }
+}
+
 impl<T: Copy> PrefixTrie<T> {
     /// Creates a new [`PrefixTrie`].
     pub fn new() -> Self {
@@ -34,6 +50,24 @@ impl<T: Copy> PrefixTrie<T> {
         }
     }
 
+    /// Estimates the number of bytes occupied by the trie, including its heap allocations.
+    pub fn approximate_size(&self) -> usize {
+        let children: usize = self
+            .children
+            .values()
+            .map(|child| child.approximate_size() - size_of::<Self>())
+            .sum();
+        let wildcard = self
+            .wildcard
+            .as_ref()
+            .map_or(0, |child| child.approximate_size());
+        // Every bucket of a HashMap carries one control byte besides its entry.
+        size_of::<Self>()
+            + self.children.capacity() * (size_of::<(char, Self)>() + 1)
+            + children
+            + wildcard
+    }
+
     /// Inserts a value at a given prefix.
     /// `*` symbols are interpreted as wildcards.
     /// A `\0` byte allows only for exact matching. (Anything after a `\0` byte is ignored.)
@@ -70,6 +104,33 @@ impl<T: Copy> PrefixTrie<T> {
         }
     }
 
+    /// Lists all inserted keys along with their values, in no particular order.
+    /// Wildcards are given as `*` and exact matches end in `\0`, like they were inserted.
+    pub fn entries(&self) -> Vec<(String, T)> {
+        let mut entries = Vec::new();
+        self.collect_entries(&mut String::new(), &mut entries);
+        entries
+    }
+
+    fn collect_entries(&self, prefix: &mut String, entries: &mut Vec<(String, T)>) {
+        if let Some(value) = self.value {
+            entries.push((prefix.clone(), value));
+        }
+        if let Some(exact) = self.exact {
+            entries.push((format!("{prefix}\0"), exact));
+        }
+        if let Some(wild) = &self.wildcard {
+            prefix.push('*');
+            wild.collect_entries(prefix, entries);
+            prefix.pop();
+        }
+        for (letter, child) in &self.children {
+            prefix.push(*letter);
+            child.collect_entries(prefix, entries);
+            prefix.pop();
+        }
+    }
+
     /// Gets the value with the most precise matching prefix
     pub fn get(&self, key: &str) -> Option<T> {
         self.get_chars(key.chars())
@@ -77,19 +138,29 @@ impl<T: Copy> PrefixTrie<T> {
 
     /// Gets the value with the most precise matching prefix
     pub fn get_chars(&self, key: impl Iterator<Item = char> + Clone) -> Option<T> {
-        self.get_chars_depth(key).map(|val| val.0)
+        self.get_chars_depth(key, &Some).map(|val| val.0)
+    }
+
+    /// Gets the value with the most precise matching prefix, considering only the values `select` maps to `Some`.
+    /// Values mapped to `None` are treated as if they weren't inserted.
+    pub fn get_by<U: Copy>(&self, key: &str, select: impl Fn(T) -> Option<U>) -> Option<U> {
+        self.get_chars_depth(key.chars(), &select).map(|val| val.0)
     }
 
-    fn get_chars_depth(&self, mut key: impl Iterator<Item = char> + Clone) -> Option<(T, usize)> {
-        let mut biggest_result: Option<(T, usize)> = None;
+    fn get_chars_depth<U: Copy>(
+        &self,
+        mut key: impl Iterator<Item = char> + Clone,
+        select: &impl Fn(T) -> Option<U>,
+    ) -> Option<(U, usize)> {
+        let mut biggest_result: Option<(U, usize)> = None;
 
         if let Some(wild) = &self.wildcard {
             let mut sub_key = key.clone();
             loop {
-                if let Some(result) = wild.get_chars_depth(sub_key.clone()) {
-                    if biggest_result.is_none() || biggest_result.unwrap().1 > result.1 {
-                        biggest_result = Some(result);
-                    }
+                if let Some(result) = wild.get_chars_depth(sub_key.clone(), select)
+                    && (biggest_result.is_none() || biggest_result.unwrap().1 > result.1)
+                {
+                    biggest_result = Some(result);
                 }
                 if sub_key.next().is_none() {
                     break;
@@ -98,14 +169,13 @@ impl<T: Copy> PrefixTrie<T> {
         }
 
         if let Some(letter) = key.next() {
-            if let Some(child) = self.children.get(&letter) {
-                if let Some(result) = child.get_chars_depth(key.clone()) {
-                    if biggest_result.is_none() || biggest_result.unwrap().1 > result.1 {
-                        biggest_result = Some(result);
-                    }
-                }
+            if let Some(child) = self.children.get(&letter)
+                && let Some(result) = child.get_chars_depth(key.clone(), select)
+                && (biggest_result.is_none() || biggest_result.unwrap().1 > result.1)
+            {
+                biggest_result = Some(result);
             }
-        } else if let Some(exact) = self.exact {
+        } else if let Some(exact) = self.exact.and_then(select) {
             return Some((exact, 1000));
         }
 
@@ -113,7 +183,71 @@ impl<T: Copy> PrefixTrie<T> {
             return Some((result.0, result.1 + 1));
         }
 
-        self.value.map(|value| (value, 0))
+        self.value.and_then(select).map(|value| (value, 0))
+    }
+
+    /// Gets the value with the most precise matching prefix, along with the matching prefix.
+    /// Wildcards are given as `*` and exact matches end in `\0`, like they were inserted.
+    pub fn get_match(&self, key: &str) -> Option<(T, String)> {
+        self.get_match_by(key, Some)
+    }
+
+    /// Like [`PrefixTrie::get_match`], but considers only the values `select` maps to `Some`.
+    pub fn get_match_by<U: Copy>(
+        &self,
+        key: &str,
+        select: impl Fn(T) -> Option<U>,
+    ) -> Option<(U, String)> {
+        self.get_chars_match(key.chars(), &select)
+            .map(|(value, _, path)| (value, path.into_iter().rev().collect()))
+    }
+
+    /// Like `get_chars_depth`, but also collects the matching prefix in reverse.
+    fn get_chars_match<U: Copy>(
+        &self,
+        mut key: impl Iterator<Item = char> + Clone,
+        select: &impl Fn(T) -> Option<U>,
+    ) -> Option<(U, usize, Vec<char>)> {
+        let mut biggest_result: Option<(U, usize, Vec<char>)> = None;
+
+        if let Some(wild) = &self.wildcard {
+            let mut sub_key = key.clone();
+            loop {
+                if let Some(mut result) = wild.get_chars_match(sub_key.clone(), select)
+                    && biggest_result
+                        .as_ref()
+                        .is_none_or(|biggest| biggest.1 > result.1)
+                {
+                    result.2.push('*');
+                    biggest_result = Some(result);
+                }
+                if sub_key.next().is_none() {
+                    break;
+                }
+            }
+        }
+
+        if let Some(letter) = key.next() {
+            if let Some(child) = self.children.get(&letter)
+                && let Some(mut result) = child.get_chars_match(key.clone(), select)
+                && biggest_result
+                    .as_ref()
+                    .is_none_or(|biggest| biggest.1 > result.1)
+            {
+                result.2.push(letter);
+                biggest_result = Some(result);
+            }
+        } else if let Some(exact) = self.exact.and_then(select) {
+            return Some((exact, 1000, vec!['\0']));
+        }
+
+        if let Some((value, depth, path)) = biggest_result {
+            return Some((value, depth + 1, path));
+        }
+
+        self.value
+            .and_then(select)
+            .map(|value| (value, 0, Vec::new()))
     }
 
     /// Checks if key can be found
@@ -126,3 +260,262 @@ impl<T: Copy> PrefixTrie<T> {
         self.get_chars(key).is_some()
     }
 }
+
+impl<T: Copy + Eq + Hash> PrefixTrie<T> {
+    /// Checks whether two tries give the same value to every key, as returned by [`PrefixTrie::get`].
+    ///
+    /// The comparison is exact: the values of both tries are computed for all keys at once, from the end
+    /// of the key to its start. Keys leading to the same values at every node are merged, so only
+    /// finitely many sets of values need to be visited.
+    pub fn equivalent(&self, other: &PrefixTrie<T>) -> bool {
+        let mut arena = Arena::default();
+        let roots = [arena.add(self, None, 0), arena.add(other, None, 0)];
+
+        let start = arena.step(None, &Suffix::default());
+        let mut seen = HashSet::from([start.clone()]);
+        let mut queue = vec![start];
+        // A step only reads the nodes its letter leads to, so suffixes differing elsewhere share it.
+        let mut steps = HashSet::new();
+        while let Some(suffix) = queue.pop() {
+            let [left, right] = roots.map(|root| arena.get(&suffix, root).map(|(value, _)| value));
+            if left != right {
+                return false;
+            }
+            // Letters leading to nothing step like a character which isn't in any key, written as `None`.
+            let mut letters: Vec<Option<char>> = arena
+                .value_edges
+                .keys()
+                .copied()
+                .chain(
+                    suffix
+                        .found
+                        .iter()
+                        .filter_map(|(idx, _)| arena.letter(*idx)),
+                )
+                .map(Some)
+                .collect();
+            letters.sort_unstable();
+            letters.dedup();
+            letters.push(None);
+            for letter in letters {
+                let read = Suffix {
+                    found: suffix
+                        .found
+                        .iter()
+                        .filter(|(idx, _)| letter.is_some() && arena.letter(*idx) == letter)
+                        .copied()
+                        .collect(),
+                    wildcard: suffix.wildcard.clone(),
+                };
+                if !steps.insert((letter, read.clone())) {
+                    continue;
+                }
+                let next = arena.step(Some(letter), &read);
+                if seen.insert(next.clone()) {
+                    queue.push(next);
+                }
+            }
+        }
+        true
+    }
+}
+
+/// A node of a [`PrefixTrie`] in an [`Arena`].
+struct ArenaNode<T> {
+    value: Option<T>,
+    exact: Option<T>,
+    wildcard: Option<usize>,
+    /// The parent of the node and the letter leading to it, `None` for a wildcard.
+    parent: Option<(usize, Option<char>)>,
+    depth: usize,
+}
+
+/// The nodes of tries indexed by number, for [`PrefixTrie::equivalent`].
+struct Arena<T> {
+    nodes: Vec<ArenaNode<T>>,
+    children: HashMap<(usize, char), usize>,
+    /// The nodes with a value, by the letter leading to them.
+    value_edges: HashMap<char, Vec<usize>>,
+    /// The nodes with a wildcard child.
+    wildcard_parents: Vec<usize>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            children: HashMap::new(),
+            value_edges: HashMap::new(),
+            wildcard_parents: Vec::new(),
+        }
+    }
+}
+
+/// The values found by `get_chars_depth` at every node for a suffix of a key, where they differ from the value of the node itself.
+/// `wildcard` holds the best value found by the wildcard child of a node over the suffixes of the suffix.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Suffix<T> {
+    found: Vec<(usize, Option<(T, usize)>)>,
+    wildcard: Vec<(usize, (T, usize))>,
+}
+
+impl<T> Default for Suffix<T> {
+    fn default() -> Self {
+        Self {
+            found: Vec::new(),
+            wildcard: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> Arena<T> {
+    fn add(
+        &mut self,
+        trie: &PrefixTrie<T>,
+        parent: Option<(usize, Option<char>)>,
+        depth: usize,
+    ) -> usize {
+        let idx = self.nodes.len();
+        self.nodes.push(ArenaNode {
+            value: trie.value,
+            exact: trie.exact,
+            wildcard: None,
+            parent,
+            depth,
+        });
+        if let Some(wild) = &trie.wildcard {
+            let child = self.add(wild, Some((idx, None)), depth + 1);
+            self.nodes[idx].wildcard = Some(child);
+            self.wildcard_parents.push(idx);
+        }
+        for (letter, child) in &trie.children {
+            let child_idx = self.add(child, Some((idx, Some(*letter))), depth + 1);
+            self.children.insert((idx, *letter), child_idx);
+            if child.value.is_some() {
+                self.value_edges.entry(*letter).or_default().push(child_idx);
+            }
+        }
+        idx
+    }
+
+    /// Returns the letter leading to a node, unless it is the root or a wildcard.
+    fn letter(&self, idx: usize) -> Option<char> {
+        self.nodes[idx].parent.and_then(|(_, letter)| letter)
+    }
+
+    fn own(&self, idx: usize) -> Option<(T, usize)> {
+        self.nodes[idx].value.map(|value| (value, 0))
+    }
+
+    fn get(&self, suffix: &Suffix<T>, idx: usize) -> Option<(T, usize)> {
+        match suffix.found.binary_search_by_key(&idx, |(node, _)| *node) {
+            Ok(pos) => suffix.found[pos].1,
+            Err(_) => self.own(idx),
+        }
+    }
+
+    fn wildcard(suffix: &Suffix<T>, idx: usize) -> Option<(T, usize)> {
+        suffix
+            .wildcard
+            .binary_search_by_key(&idx, |(node, _)| *node)
+            .ok()
+            .map(|pos| suffix.wildcard[pos].1)
+    }
+
+    /// Computes the values for `letter` followed by `rest`, or for the empty suffix if `letter` is `None`.
+    /// A letter of `Some(None)` stands for any character which isn't in a key.
+    fn step(&self, letter: Option<Option<char>>, rest: &Suffix<T>) -> Suffix<T> {
+        // Only nodes with a wildcard child or leading to a value by `letter` can find anything but their own value.
+        let mut candidates = self.wildcard_parents.clone();
+        match letter {
+            None => candidates
+                .extend((0..self.nodes.len()).filter(|idx| self.nodes[*idx].exact.is_some())),
+            Some(Some(letter)) => {
+                let leading = rest
+                    .found
+                    .iter()
+                    .map(|(idx, _)| *idx)
+                    .chain(self.value_edges.get(&letter).into_iter().flatten().copied());
+                candidates.extend(leading.filter_map(|idx| match self.nodes[idx].parent {
+                    Some((parent, Some(edge))) if edge == letter => Some(parent),
+                    _ => None,
+                }));
+            }
+            Some(None) => {}
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        // Wildcard children are deeper than their parents and found for the same suffix, so they go first.
+        candidates.sort_unstable_by_key(|idx| std::cmp::Reverse(self.nodes[*idx].depth));
+
+        let mut found: HashMap<usize, Option<(T, usize)>> = HashMap::new();
+        let mut wildcard = Vec::new();
+        for idx in candidates {
+            let node = &self.nodes[idx];
+            let wild = node.wildcard.and_then(|child| {
+                let here = found
+                    .get(&child)
+                    .copied()
+                    .unwrap_or_else(|| self.own(child));
+                let later = letter.and(Self::wildcard(rest, idx));
+                // Like `get_chars_depth`, the longest suffix wins a tie.
+                match (here, later) {
+                    (Some(here), Some(later)) if here.1 > later.1 => Some(later),
+                    (None, later) => later,
+                    (here, _) => here,
+                }
+            });
+            if let Some(wild) = wild {
+                wildcard.push((idx, wild));
+            }
+            let value = match letter {
+                None if node.exact.is_some() => node.exact.map(|exact| (exact, 1000)),
+                _ => {
+                    let child = match letter {
+                        Some(Some(letter)) => self
+                            .children
+                            .get(&(idx, letter))
+                            .and_then(|child| self.get(rest, *child)),
+                        _ => None,
+                    };
+                    let best = match (wild, child) {
+                        (Some(wild), Some(child)) if wild.1 > child.1 => Some(child),
+                        (None, child) => child,
+                        (wild, _) => wild,
+                    };
+                    best.map(|(value, depth)| (value, depth + 1))
+                        .or_else(|| self.own(idx))
+                }
+            };
+            if value != self.own(idx) {
+                found.insert(idx, value);
+            }
+        }
+
+        let mut found: Vec<_> = found.into_iter().collect();
+        found.sort_unstable_by_key(|(idx, _)| *idx);
+        wildcard.sort_unstable_by_key(|(idx, _)| *idx);
+        Suffix { found, wildcard }
+    }
+}
+
+/// A character standing in for the text matched by a wildcard, chosen so that it is unlikely to be matched by other keys.
+const FILLER: char = '\u{fffd}';
+
+/// Returns example keys matched by an inserted key, the wildcards replaced by nothing and by a filler.
+/// Unless the key is exact, keys extended by a filler are included as well.
+pub(crate) fn witnesses(key: &str) -> Vec<String> {
+    let (prefix, exact) = match key.split_once('\0') {
+        Some((prefix, _)) => (prefix, true),
+        None => (key, false),
+    };
+    let mut witnesses = vec![
+        prefix.replace('*', ""),
+        prefix.replace('*', &FILLER.to_string()),
+    ];
+    if !exact {
+        witnesses.push(format!("{}{FILLER}", witnesses[0]));
+        witnesses.push(format!("{}{FILLER}", witnesses[1]));
+    }
+    witnesses
+}
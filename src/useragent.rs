@@ -0,0 +1,133 @@
+/// Products and comment items of browsers and platforms, which never name a crawler.
+const GENERIC_TOKENS: [&str; 34] = [
+    "mozilla",
+    "compatible",
+    "applewebkit",
+    "khtml",
+    "gecko",
+    "chrome",
+    "chromium",
+    "safari",
+    "version",
+    "mobile",
+    "firefox",
+    "edg",
+    "edge",
+    "opr",
+    "opera",
+    "msie",
+    "trident",
+    "windows",
+    "win64",
+    "wow64",
+    "x64",
+    "macintosh",
+    "intel",
+    "linux",
+    "x11",
+    "ubuntu",
+    "android",
+    "iphone",
+    "ipad",
+    "cpu",
+    "u",
+    "en",
+    "en-us",
+    "headlesschrome",
+];
+
+/// The product tokens of a `User-Agent` header, like `ExampleBot` in `Mozilla/5.0 (compatible; ExampleBot/2.1; +https://example.com/bot)`.
+///
+/// Tokens are taken from products and comment items, skipping versions, URLs and the usual browser and platform names.
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub struct UserAgent {
+    tokens: Vec<String>,
+}
+
+impl UserAgent {
+    /// Parses a `User-Agent` header. A bare product token like `Googlebot` is taken as is.
+    pub fn parse(header: &str) -> Self {
+        let mut tokens: Vec<String> = Vec::new();
+        for item in items(header) {
+            let token = item.split('/').next().unwrap_or_default().trim();
+            if is_product_token(token)
+                && !GENERIC_TOKENS.contains(&token.to_ascii_lowercase().as_str())
+                && !tokens.iter().any(|known| known.eq_ignore_ascii_case(token))
+            {
+                tokens.push(token.to_owned());
+            }
+        }
+        Self { tokens }
+    }
+
+    /// Returns the product tokens as written, in order of appearance.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Returns the first product token, which usually names the crawler.
+    pub fn product_token(&self) -> Option<&str> {
+        self.tokens.first().map(String::as_str)
+    }
+
+    /// Returns the user agents to select groups with in order of preference, in lowercase.
+    ///
+    /// Every product token comes first, followed by its fallbacks without the trailing `-` parts,
+    /// e.g. `googlebot-image` and `googlebot` for `Googlebot-Image`. If none is named by a `robots.txt`, `*` applies.
+    pub fn candidates(&self) -> Vec<String> {
+        let mut candidates: Vec<String> = Vec::new();
+        let fallbacks = self.tokens.iter().flat_map(|token| {
+            token
+                .char_indices()
+                .filter(|(_, ch)| *ch == '-')
+                .map(|(idx, _)| &token[..idx])
+                .rev()
+        });
+        for candidate in self.tokens.iter().map(String::as_str).chain(fallbacks) {
+            let candidate = candidate.to_lowercase();
+            if !candidate.is_empty() && !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// Splits a header into products and the items of their comments, keeping the order.
+fn items(header: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut rest = header;
+    while let Some(start) = rest.find(|ch: char| !ch.is_whitespace()) {
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix('(') {
+            let mut depth = 1;
+            let end = comment
+                .char_indices()
+                .find(|(_, ch)| {
+                    match ch {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .map_or(comment.len(), |(idx, _)| idx);
+            items.extend(comment[..end].split([';', ',']).map(str::trim));
+            rest = comment.get(end + 1..).unwrap_or_default();
+        } else {
+            let end = rest.find([' ', '\t', '(']).unwrap_or(rest.len());
+            items.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    items
+}
+
+/// Checks whether an item is a product token, rather than a URL, an email address or a description with spaces.
+fn is_product_token(token: &str) -> bool {
+    !token.is_empty()
+        && token.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && token
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+}
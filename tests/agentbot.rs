@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nicebot::{AgentBot, ParseProfile, Permission, RobotsTxt, SingleBot};
+
+    const AGENTS: [Option<&str>; 6] = [
+        None,
+        Some("googlebot"),
+        Some("bingbot"),
+        Some("gptbot"),
+        Some("yandex"),
+        Some("nicebot"),
+    ];
+
+    const PROFILES: [ParseProfile; 5] = [
+        ParseProfile::Classic,
+        ParseProfile::Rfc9309,
+        ParseProfile::Google,
+        ParseProfile::Bing,
+        ParseProfile::Yandex,
+    ];
+
+    /// Returns paths covered by the rules of a `robots.txt`, and paths just beyond them.
+    fn paths(robots_txt: &str) -> Vec<String> {
+        RobotsTxt::parse(robots_txt)
+            .groups
+            .iter()
+            .flat_map(|group| &group.rules)
+            .flat_map(|rule| {
+                let path = rule.example_path();
+                [format!("{path}x"), path]
+            })
+            .chain(["/".to_owned()])
+            .collect()
+    }
+
+    #[test]
+    fn same_as_singlebot() {
+        for entry in std::fs::read_dir("./test-data").unwrap() {
+            let robots_txt = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            let paths = paths(&robots_txt);
+
+            for profile in PROFILES {
+                let mut bot = AgentBot::with_profile(profile);
+                bot += robots_txt.as_str();
+
+                for agent in AGENTS {
+                    let mut single = SingleBot::with_profile(agent.map(str::to_owned), profile);
+                    single += robots_txt.as_str();
+                    let view = bot.for_agent(agent);
+
+                    for path in &paths {
+                        assert_eq!(
+                            view.explain(path),
+                            single.explain(path),
+                            "{profile:?} {agent:?} {path}"
+                        );
+                    }
+                    assert_eq!(view.crawl_delay(), single.crawl_delay());
+                    assert_eq!(view.request_rate(), single.request_rate());
+                    assert_eq!(view.content_signals(), single.content_signals());
+                    assert_eq!(view.sitemaps(), single.sitemaps());
+                }
+            }
+        }
+    }
+
+    const ROBOTS: &str = r"
+            User-agent: *
+            Disallow: /private
+            Crawl-delay: 5
+
+            User-agent: GPTBot
+            Content-Signal: ai-train=no
+            Disallow: /
+
+            User-agent: Googlebot
+            Allow: /private/press
+            Disallow: /private
+            ";
+
+    #[test]
+    fn check_any_agent() {
+        let bot = AgentBot::from(ROBOTS);
+
+        assert_eq!(bot.check("nicebot", "/private"), Permission::Denied);
+        assert_eq!(bot.check("nicebot", "/public"), Permission::Unspecified);
+        assert_eq!(bot.check("GPTBot", "/public"), Permission::Denied);
+        assert_eq!(
+            bot.check("googlebot", "/private/press"),
+            Permission::Allowed
+        );
+        assert_eq!(bot.check("googlebot", "/private"), Permission::Denied);
+        assert_eq!(
+            bot.explain("googlebot", "/private/press/1").rule.as_deref(),
+            Some("/private/press")
+        );
+        assert_eq!(
+            bot.for_agent(Some("nicebot")).crawl_delay(),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(bot.for_agent(Some("googlebot")).crawl_delay(), None);
+        assert_eq!(
+            bot.for_agent(Some("gptbot"))
+                .content_signals()
+                .and_then(|signals| signals.ai_train),
+            Some(false)
+        );
+        assert_eq!(bot.for_agent(None).content_signals(), None);
+        assert_eq!(bot.user_agents(), vec!["gptbot", "googlebot"]);
+
+        let mut rules = bot.for_agent(Some("googlebot")).rules();
+        rules.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            rules,
+            vec![
+                ("/private".to_owned(), Permission::Denied),
+                ("/private/press".to_owned(), Permission::Allowed),
+            ]
+        );
+        assert_eq!(
+            bot.for_agent(Some("gptbot")).rules(),
+            vec![("/".to_owned(), Permission::Denied)]
+        );
+    }
+
+    #[cfg(feature = "meta")]
+    #[test]
+    fn unreachable() {
+        let mut bot = AgentBot::from(ROBOTS);
+        bot += nicebot::FetchOutcome::Unreachable;
+        assert_eq!(bot.check("googlebot", "/public"), Permission::Denied);
+        assert_eq!(
+            bot.check("googlebot", "/private/press"),
+            Permission::Allowed
+        );
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(all(test, feature = "meta", feature = "sitemap"))]
+mod tests {
+    use nicebot::{
+        AddRobots, MetaBot, Permission, SingleBot, SitemapAudit, SitemapReader, Verdict,
+    };
+    use url::Host;
+
+    const ROBOTS: &str = r"
+        User-agent: *
+        Disallow: /private/
+        Allow: /private/press/
+        Disallow: /*.pdf$
+    ";
+
+    const SITEMAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <url><loc>https://example.com/</loc></url>
+            <url><loc>https://example.com/private/team</loc></url>
+            <url><loc>https://example.com/private/press/launch</loc></url>
+            <url><loc>https://example.com/docs/manual.pdf</loc></url>
+            <url><loc>/relative</loc></url>
+        </urlset>
+    "#;
+
+    fn locs(items: &[nicebot::AuditedItem]) -> Vec<&str> {
+        items.iter().map(|audited| audited.item.loc()).collect()
+    }
+
+    #[test]
+    fn explain() {
+        let bot = SingleBot::from(ROBOTS);
+
+        assert_eq!(
+            bot.explain("/private/press/x"),
+            Verdict {
+                permission: Permission::Allowed,
+                rule: Some("/private/press/".into())
+            }
+        );
+        assert_eq!(bot.explain("/a.pdf").rule.as_deref(), Some("/*.pdf$"));
+        assert_eq!(bot.explain("/public"), Verdict::default());
+    }
+
+    #[test]
+    fn audit_singlebot() {
+        let bot = SingleBot::from(ROBOTS);
+        let audit = SitemapAudit::try_audit(&bot, SitemapReader::from(SITEMAP)).unwrap();
+
+        assert_eq!(
+            locs(&audit.allowed),
+            [
+                "https://example.com/",
+                "https://example.com/private/press/launch"
+            ]
+        );
+        assert_eq!(
+            locs(&audit.denied),
+            [
+                "https://example.com/private/team",
+                "https://example.com/docs/manual.pdf"
+            ]
+        );
+        assert_eq!(audit.denied[0].verdict.rule.as_deref(), Some("/private/"));
+        assert_eq!(audit.denied[1].verdict.rule.as_deref(), Some("/*.pdf$"));
+        assert_eq!(audit.invalid.len(), 1);
+        assert_eq!(audit.len(), 5);
+        assert!(!audit.is_consistent());
+    }
+
+    #[test]
+    fn audit_metabot() {
+        let mut meta = MetaBot::new(None);
+        meta.add_robots(Host::Domain("example.com".into()), ROBOTS);
+
+        let items =
+            SitemapReader::from("https://example.com/private/x\nhttps://other.com/private/x\n")
+                .map(Result::unwrap);
+        let audit = SitemapAudit::audit(&meta, items);
+
+        assert_eq!(locs(&audit.denied), ["https://example.com/private/x"]);
+        assert_eq!(locs(&audit.allowed), ["https://other.com/private/x"]);
+        assert_eq!(audit.allowed[0].verdict.rule, None);
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(all(test, feature = "meta"))]
+mod tests {
+    use nicebot::{BoundedMetaBot, MetaBot, ParseProfile, Permission, TryAddRobots};
+    use url::Host;
+
+    const ROBOTS: &str = r#"
+        User-agent: *
+        Disallow: /private
+    "#;
+
+    fn host(name: &str) -> Host<String> {
+        Host::parse(name).unwrap()
+    }
+
+    #[test]
+    fn evicts_least_recently_checked() {
+        let mut bot = BoundedMetaBot::new(None, Some(2), None);
+        bot.try_add_robots("a.example", ROBOTS);
+        bot.try_add_robots("b.example", ROBOTS);
+
+        assert_eq!(
+            bot.check("https://a.example/private"),
+            Ok(Permission::Denied)
+        );
+
+        bot.try_add_robots("c.example", ROBOTS);
+
+        assert_eq!(bot.len(), 2);
+        assert_eq!(
+            bot.drain_evicted().collect::<Vec<_>>(),
+            vec![host("b.example")]
+        );
+        assert_eq!(bot.drain_evicted().count(), 0);
+        assert_eq!(
+            bot.check("https://b.example/private"),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(
+            bot.check("https://a.example/private"),
+            Ok(Permission::Denied)
+        );
+    }
+
+    #[test]
+    fn peek_does_not_touch() {
+        let mut bot = BoundedMetaBot::new(None, Some(2), None);
+        bot.try_add_robots("a.example", ROBOTS);
+        bot.try_add_robots("b.example", ROBOTS);
+
+        assert_eq!(
+            bot.peek("https://a.example/private"),
+            Ok(Permission::Denied)
+        );
+
+        bot.try_add_robots("c.example", ROBOTS);
+
+        assert_eq!(
+            bot.drain_evicted().collect::<Vec<_>>(),
+            vec![host("a.example")]
+        );
+    }
+
+    #[test]
+    fn byte_budget() {
+        let tor = std::fs::read_to_string("test-data/tor.txt").unwrap();
+
+        let mut unbounded = BoundedMetaBot::new(None, None, None);
+        unbounded.try_add_robots("a.example", tor.as_str());
+        let size = unbounded.approximate_size();
+        assert!(size > 0);
+
+        // Hosts with the same robots.txt share its bytes.
+        let mut bot = BoundedMetaBot::new(None, None, Some(size * 3 / 2));
+        bot.try_add_robots("a.example", tor.as_str());
+        bot.try_add_robots("b.example", tor.as_str());
+
+        assert_eq!(bot.len(), 2);
+        assert!(bot.approximate_size() <= size * 3 / 2);
+        assert_eq!(bot.drain_evicted().count(), 0);
+
+        // The shared bytes are only freed with the last host using them.
+        let other = format!("{tor}\nDisallow: /other");
+        bot.try_add_robots("c.example", other.as_str());
+
+        assert_eq!(bot.len(), 1);
+        assert!(bot.approximate_size() <= size * 3 / 2);
+        assert_eq!(
+            bot.drain_evicted().collect::<Vec<_>>(),
+            vec![host("a.example"), host("b.example")]
+        );
+
+        bot.remove(&host("c.example"));
+        assert!(bot.is_empty());
+        assert_eq!(bot.approximate_size(), 0);
+    }
+
+    #[test]
+    fn configured_meta() {
+        const TYPO: &str = "User-agent: *\nDissallow: /private";
+
+        let mut meta = MetaBot::with_profile(None, ParseProfile::Google);
+        for name in ["a.example", "b.example", "c.example"] {
+            meta.try_add_robots(name, TYPO);
+        }
+        let mut bounded = BoundedMetaBot::with_meta(meta, Some(2), None);
+        assert_eq!(bounded.len(), 2);
+        assert_eq!(bounded.drain_evicted().count(), 1);
+
+        bounded.try_add_robots("d.example", TYPO);
+        assert_eq!(bounded.meta().profile(), ParseProfile::Google);
+        assert_eq!(
+            bounded.check("https://d.example/private"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(bounded.len(), 2);
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use nicebot::{AgentBot, CleanParam, ParseProfile, SingleBot, diagnose};
+
+    #[test]
+    fn parse() {
+        let clean_param = CleanParam::parse("ref&sid /forum/*.php").unwrap();
+        assert_eq!(clean_param.params, ["ref", "sid"]);
+        assert_eq!(clean_param.prefix, "/forum/*.php");
+        assert!(clean_param.covers("/forum/board/topic.php"));
+        assert!(clean_param.covers("/forum/a.php5"));
+        assert!(!clean_param.covers("/forum/topic.html"));
+        assert!(!clean_param.covers("/blog/a.php"));
+
+        let everywhere = CleanParam::parse("utm_source").unwrap();
+        assert!(everywhere.covers("/"));
+        assert_eq!(CleanParam::parse(""), None);
+        assert_eq!(CleanParam::parse("& /path"), None);
+    }
+
+    #[test]
+    fn clean_path() {
+        let robots_txt = "\
+User-agent: *
+Disallow: /private
+Clean-param: utm_source&utm_medium
+Clean-param: sid /forum/
+Host: www.example.com
+Host: mirror.example.com
+";
+
+        let mut single = SingleBot::new(Some("examplebot".to_owned()));
+        single += robots_txt;
+        let agents = AgentBot::from(robots_txt);
+
+        for (path, cleaned) in [
+            ("/a?utm_source=x&id=1&utm_medium=y", "/a?id=1"),
+            ("/a?utm_source=x#top", "/a#top"),
+            ("/forum/topic?sid=1&page=2", "/forum/topic?page=2"),
+            ("/blog?sid=1", "/blog?sid=1"),
+            ("/plain", "/plain"),
+        ] {
+            assert_eq!(single.clean_path(path), cleaned, "{path}");
+            assert_eq!(agents.clean_path(path), cleaned, "{path}");
+        }
+        assert_eq!(single.host(), Some("www.example.com"));
+        assert_eq!(agents.host(), Some("www.example.com"));
+        assert_eq!(single.clean_params().len(), 2);
+        assert!(diagnose(robots_txt).is_empty());
+    }
+
+    #[test]
+    fn profiles() {
+        let robots_txt = std::fs::read_to_string("test-data/yandex.txt").unwrap();
+
+        let yandex = {
+            let mut bot =
+                SingleBot::with_profile(Some("yandexbot".to_owned()), ParseProfile::Yandex);
+            bot += robots_txt.as_str();
+            bot
+        };
+        assert_eq!(
+            yandex.clean_path("/maps/?ll=37.6,55.7&q=cafe&z=10"),
+            "/maps/?q=cafe"
+        );
+        assert_eq!(yandex.clean_path("/games/?lang=ru&id=1"), "/games/?id=1");
+
+        let mut google =
+            SingleBot::with_profile(Some("googlebot".to_owned()), ParseProfile::Google);
+        google += robots_txt.as_str();
+        assert!(google.clean_params().is_empty());
+        assert_eq!(
+            google.clean_path("/maps/?ll=37.6&z=10"),
+            "/maps/?ll=37.6&z=10"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(all(test, feature = "cli"))]
+mod tests {
+    use std::{
+        io::Write,
+        path::PathBuf,
+        process::{Command, Output, Stdio},
+    };
+
+    fn nicebot(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_nicebot"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8(output.stdout.clone()).unwrap()
+    }
+
+    #[test]
+    fn file_arguments() {
+        let output = nicebot(
+            &["test-data/lib.txt", "https://lib.rs/about", "/crates/faker"],
+            "",
+        );
+
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            stdout(&output),
+            "unspecified https://lib.rs/about\ndenied      /crates/faker  (/crates/faker)\n"
+        );
+    }
+
+    #[test]
+    fn user_agent() {
+        let output = nicebot(&["-a", "AhrefsBot", "test-data/lib.txt", "/about"], "");
+
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(stdout(&output), "denied      /about  (/)\n");
+
+        let output = nicebot(
+            &["--user-agent=ExampleBot", "test-data/lib.txt", "/about"],
+            "",
+        );
+
+        assert_eq!(output.status.code(), Some(0));
+    }
+
+    #[test]
+    fn json_from_stdin() {
+        let output = nicebot(
+            &["--json", "test-data/lib.txt"],
+            "https://lib.rs/search?q=\"a\"\n\nnot a url\n",
+        );
+
+        assert_eq!(output.status.code(), Some(2));
+        let lines: Vec<_> = stdout(&output).lines().map(str::to_owned).collect();
+        assert_eq!(
+            lines[0],
+            r#"{"url":"https://lib.rs/search?q=\"a\"","permission":"denied","rule":"/search"}"#
+        );
+        assert!(lines[1].starts_with(r#"{"url":"not a url","error":"#));
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn directory() {
+        let dir: PathBuf = std::env::temp_dir().join(format!("nicebot-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::copy("test-data/lib.txt", dir.join("lib.rs.txt")).unwrap();
+        std::fs::copy("test-data/reddit.txt", dir.join("www.reddit.com")).unwrap();
+
+        let output = nicebot(
+            &[
+                dir.to_str().unwrap(),
+                "https://lib.rs/install/x",
+                "https://www.reddit.com/",
+                "https://example.com/install/x",
+            ],
+            "",
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(output.status.code(), Some(1));
+        let lines: Vec<_> = stdout(&output).lines().map(str::to_owned).collect();
+        assert_eq!(
+            lines[0],
+            "denied      https://lib.rs/install/x  (/install/)"
+        );
+        assert!(lines[1].starts_with("denied      https://www.reddit.com/"));
+        assert_eq!(lines[2], "unspecified https://example.com/install/x");
+    }
+
+    #[test]
+    fn usage_errors() {
+        assert_eq!(nicebot(&[], "").status.code(), Some(2));
+        assert_eq!(nicebot(&["--bogus", "x"], "").status.code(), Some(2));
+        assert_eq!(
+            nicebot(&["test-data/missing.txt", "/"], "").status.code(),
+            Some(2)
+        );
+        let help = nicebot(&["--help"], "");
+        assert_eq!(help.status.code(), Some(0));
+        assert!(stdout(&help).starts_with("Usage: nicebot"));
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(all(test, feature = "meta"))]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use nicebot::{CheckURL, ConcurrentMetaBot, Permission, SingleBot};
+    use url::Host;
+
+    const ALLOW: &str = r#"
+        User-agent: *
+        Allow: /page
+    "#;
+
+    const DENY: &str = r#"
+        User-agent: *
+        Disallow: /page
+    "#;
+
+    #[test]
+    fn replace() {
+        let meta = ConcurrentMetaBot::new(None);
+        let host = Host::parse("a.example").unwrap();
+
+        assert!(meta.replace_robots(host.clone(), ALLOW).is_none());
+        assert_eq!(
+            meta.check("https://a.example/page"),
+            Ok(Permission::Allowed)
+        );
+
+        let previous = meta.replace_robots(host.clone(), DENY).unwrap();
+        assert_eq!(previous.check("/page"), Permission::Allowed);
+        assert_eq!(meta.check("https://a.example/page"), Ok(Permission::Denied));
+
+        assert!(meta.remove(&host).is_some());
+        assert!(meta.is_empty());
+        assert_eq!(
+            meta.check("https://a.example/page"),
+            Ok(Permission::Unspecified)
+        );
+    }
+
+    #[test]
+    fn expiry() {
+        let mut meta = ConcurrentMetaBot::new(None);
+        meta.set_ttl(Duration::from_secs(3_600));
+        let host = Host::parse("a.example").unwrap();
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        meta.insert_with_expiry(
+            host.clone(),
+            SingleBot::from(DENY),
+            fetched_at,
+            Duration::MAX,
+        );
+        assert_eq!(
+            meta.expires_at(&host),
+            Some(fetched_at + Duration::from_secs(3_600))
+        );
+        let expired = fetched_at + Duration::from_secs(3_600);
+        assert_eq!(meta.is_stale(&host, expired), Some(true));
+        assert!(meta.get_fresh(&host, expired).is_none());
+        assert!(meta.get_fresh(&host, fetched_at).is_some());
+        assert_eq!(meta.stale_hosts(expired), std::slice::from_ref(&host));
+
+        meta.set_expiry_from_headers(&host, fetched_at, Some("max-age=60"), None);
+        assert_eq!(
+            meta.stale_hosts(fetched_at + Duration::from_secs(60)),
+            [host]
+        );
+    }
+
+    #[test]
+    fn stress() {
+        const HOSTS: usize = 32;
+
+        let meta = ConcurrentMetaBot::with_shards(None, 8);
+        let allow = SingleBot::from(ALLOW);
+        let deny = SingleBot::from(DENY);
+        for i in 0..HOSTS {
+            meta.insert(
+                Host::parse(&format!("host{i}.example")).unwrap(),
+                allow.clone(),
+            );
+        }
+        let done = AtomicBool::new(false);
+
+        std::thread::scope(|scope| {
+            let writers: Vec<_> = (0..4)
+                .map(|writer| {
+                    let meta = &meta;
+                    let (allow, deny) = (&allow, &deny);
+                    scope.spawn(move || {
+                        for round in 0..2_000 {
+                            let i = (round * 7 + writer) % HOSTS;
+                            let host = Host::parse(&format!("host{i}.example")).unwrap();
+                            let bot = if round % 2 == 0 { deny } else { allow };
+                            meta.insert(host, bot.clone());
+                        }
+                    })
+                })
+                .collect();
+
+            let readers: Vec<_> = (0..8)
+                .map(|reader| {
+                    let (meta, done) = (&meta, &done);
+                    scope.spawn(move || {
+                        let mut checks = 0usize;
+                        while !done.load(Ordering::Relaxed) || checks < 1_000 {
+                            let i = (checks + reader) % HOSTS;
+                            let url = format!("https://host{i}.example/page");
+                            let permission = meta.check(url).unwrap();
+                            assert!(matches!(
+                                permission,
+                                Permission::Allowed | Permission::Denied
+                            ));
+                            checks += 1;
+                        }
+                    })
+                })
+                .collect();
+
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            done.store(true, Ordering::Relaxed);
+            for reader in readers {
+                reader.join().unwrap();
+            }
+        });
+
+        assert_eq!(meta.len(), HOSTS);
+    }
+}
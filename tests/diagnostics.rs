@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use nicebot::{
+        DiagnosticKind, ParseProfile, Recogniser, Severity, diagnose, diagnose_with,
+        diagnose_with_recogniser,
+    };
+
+    #[test]
+    fn reports_lines() {
+        const INPUT: &str = "\
+Disallow: /early
+# comment only
+
+User-agent: *
+Disallow /missing-colon
+Noindex: /foo
+Disallow: /with space
+Allow: relative
+Allow: *.css
+Disallow:
+Sitemap: /sitemap.xml
+Sitemap: https://example.com/sitemap.xml
+";
+
+        let diagnostics = diagnose(INPUT);
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.kind))
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                (1, DiagnosticKind::RuleOutsideGroup),
+                (5, DiagnosticKind::MissingColon),
+                (6, DiagnosticKind::UnknownDirective),
+                (7, DiagnosticKind::ValueWithSpaces),
+                (8, DiagnosticKind::PatternWithoutSlash),
+                (11, DiagnosticKind::RelativeSitemap),
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[2].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[2].to_string(),
+            "line 6: warning: unknown directive `Noindex`, the line is ignored"
+        );
+    }
+
+    #[test]
+    fn reports_corrections() {
+        const INPUT: &str = "\
+User agent: *
+Dissallow: /private
+Disallow /missing-colon
+Disallow: /with space
+Noindex: /foo
+";
+
+        let strict: Vec<_> = diagnose(INPUT)
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.kind))
+            .collect();
+        assert_eq!(
+            strict,
+            [
+                (1, DiagnosticKind::UnknownDirective),
+                (2, DiagnosticKind::UnknownDirective),
+                (3, DiagnosticKind::MissingColon),
+                (4, DiagnosticKind::ValueWithSpaces),
+                (5, DiagnosticKind::UnknownDirective),
+            ]
+        );
+
+        let diagnostics = diagnose_with(INPUT, ParseProfile::Google);
+        let lenient: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.kind, diagnostic.severity))
+            .collect();
+        assert_eq!(
+            lenient,
+            [
+                (1, DiagnosticKind::MisspelledDirective, Severity::Warning),
+                (2, DiagnosticKind::MisspelledDirective, Severity::Warning),
+                (3, DiagnosticKind::MissingColon, Severity::Warning),
+                (4, DiagnosticKind::ValueWithSpaces, Severity::Warning),
+                (5, DiagnosticKind::UnknownDirective, Severity::Warning),
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "line 2: warning: misspelled directive `dissallow`, it is read as `disallow`"
+        );
+    }
+
+    #[test]
+    fn profiles() {
+        const INPUT: &str = "Disallow: /early\nUser-agent: *\nAllow: relative\n";
+
+        let found = |profile| {
+            diagnose_with(INPUT, profile)
+                .into_iter()
+                .map(|diagnostic| (diagnostic.line, diagnostic.severity, diagnostic.message))
+                .collect::<Vec<_>>()
+        };
+        let ignored = (
+            1,
+            Severity::Error,
+            "`Disallow` appears before any `User-agent`, the line is ignored".to_owned(),
+        );
+        let prefixed = (
+            3,
+            Severity::Warning,
+            "the pattern `relative` doesn't start with `/`, it is read as `/relative`".to_owned(),
+        );
+
+        assert_eq!(
+            found(ParseProfile::Classic),
+            [
+                ignored.clone(),
+                (
+                    3,
+                    Severity::Warning,
+                    "the pattern `relative` doesn't start with `/`, so it never matches".to_owned()
+                )
+            ]
+        );
+        assert_eq!(
+            found(ParseProfile::Rfc9309),
+            [
+                ignored.clone(),
+                (
+                    3,
+                    Severity::Error,
+                    "the pattern `relative` doesn't start with `/`, the line is ignored".to_owned()
+                )
+            ]
+        );
+        assert_eq!(
+            found(ParseProfile::Bing),
+            [
+                (
+                    1,
+                    Severity::Warning,
+                    "`Disallow` appears before any `User-agent`, it applies to every user agent like a `*` group"
+                        .to_owned()
+                ),
+                prefixed.clone()
+            ]
+        );
+        for profile in [ParseProfile::Google, ParseProfile::Yandex] {
+            assert_eq!(found(profile), [ignored.clone(), prefixed.clone()]);
+        }
+
+        let diagnostics =
+            diagnose_with_recogniser(INPUT, ParseProfile::Classic, &Recogniser::lenient());
+        assert_eq!(diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn clean_samples() {
+        for sample in ["google.txt", "lib.txt", "github.txt"] {
+            let input = std::fs::read_to_string(format!("test-data/{sample}")).unwrap();
+            let errors: Vec<_> = diagnose(&input)
+                .into_iter()
+                .filter(|diagnostic| diagnostic.severity == Severity::Error)
+                .collect();
+
+            assert_eq!(errors, [], "{sample}");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nicebot::{Permission, RobotsDiff, RobotsTxt};
+
+    const OLD: &str = r"
+User-agent: *
+Disallow: /private/
+Crawl-delay: 5
+
+User-agent: BadBot
+Disallow: /
+
+Sitemap: https://example.com/old.xml
+";
+
+    const NEW: &str = r"
+User-agent: *
+Disallow: /private/
+Allow: /private/press/
+Disallow: /search
+
+User-agent: GPTBot
+Disallow: /
+
+Sitemap: https://example.com/new.xml
+";
+
+    #[test]
+    fn star_diff() {
+        let diff = RobotsDiff::new(&RobotsTxt::parse(OLD), &RobotsTxt::parse(NEW), None);
+
+        assert_eq!(diff.added_groups[0].user_agents, ["GPTBot"]);
+        assert_eq!(diff.removed_groups[0].user_agents, ["BadBot"]);
+        assert_eq!(diff.changed_groups[0].after.user_agents, ["*"]);
+        assert_eq!(diff.added_sitemaps, ["https://example.com/new.xml"]);
+        assert_eq!(diff.removed_sitemaps, ["https://example.com/old.xml"]);
+        assert_eq!(diff.crawl_delay, Some((Some(Duration::from_secs(5)), None)));
+
+        let changes: Vec<_> = diff
+            .permission_changes
+            .iter()
+            .map(|change| {
+                (
+                    change.path.as_str(),
+                    change.before.permission,
+                    change.after.permission,
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("/private/press/", Permission::Denied, Permission::Allowed),
+                ("/search", Permission::Unspecified, Permission::Denied),
+            ]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "\
++ group GPTBot
+- group BadBot
+~ group *
++ sitemap https://example.com/new.xml
+- sitemap https://example.com/old.xml
+~ /private/press/: denied by `/private/` -> allowed by `/private/press/`
+~ /search: unspecified -> denied by `/search`
+~ crawl-delay: Some(5s) -> None
+"
+        );
+    }
+
+    #[test]
+    fn agent_diff() {
+        let old = RobotsTxt::parse(OLD);
+        let new = RobotsTxt::parse(NEW);
+
+        let gpt = RobotsDiff::new(&old, &new, Some("GPTBot"));
+        assert_eq!(gpt.permission_changes[0].path, "/");
+        assert_eq!(
+            gpt.permission_changes[0].after.permission,
+            Permission::Denied
+        );
+
+        let bad = RobotsDiff::new(&old, &new, Some("badbot"));
+        assert_eq!(bad.permission_changes[0].path, "/");
+        assert_eq!(
+            bad.permission_changes[0].before.permission,
+            Permission::Denied
+        );
+
+        assert!(RobotsDiff::new(&new, &new, Some("GPTBot")).is_empty());
+    }
+
+    #[test]
+    fn content_signal_diff() {
+        let old = RobotsTxt::parse("User-agent: *\nContent-Signal: search=yes\nAllow: /");
+        let new =
+            RobotsTxt::parse("User-agent: *\nContent-Signal: search=yes, ai-train=no\nAllow: /");
+
+        let diff = RobotsDiff::new(&old, &new, None);
+        assert_eq!(diff.changed_groups.len(), 1);
+        assert!(diff.permission_changes.is_empty());
+        assert_eq!(
+            diff.to_string(),
+            "~ group *\n~ content-signal: search=yes -> search=yes, ai-train=no\n"
+        );
+    }
+
+    #[test]
+    fn end_anchor() {
+        let old = RobotsTxt::parse("User-agent: *\nDisallow: /private");
+        let new = RobotsTxt::parse("User-agent: *\nDisallow: /private$");
+        let diff = RobotsDiff::new(&old, &new, None);
+
+        let [change] = diff.permission_changes.as_slice() else {
+            panic!("expected one change, got {:?}", diff.permission_changes);
+        };
+        assert!(change.path.starts_with("/private") && change.path != "/private");
+        assert_eq!(change.before.permission, Permission::Denied);
+        assert_eq!(change.after.permission, Permission::Unspecified);
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(all(test, feature = "meta"))]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use nicebot::{
+        CheckURL, DEFAULT_RETRY_TTL, FetchOutcome, FetchingMetaBot, Permission, RobotsFetcher,
+        robots_url,
+    };
+    use url::{Host, Url};
+
+    /// Serves robots.txt from memory, counting every fetch.
+    struct FakeFetcher {
+        fetches: AtomicUsize,
+        delay: Duration,
+    }
+
+    impl FakeFetcher {
+        fn new(delay: Duration) -> Self {
+            Self {
+                fetches: AtomicUsize::new(0),
+                delay,
+            }
+        }
+    }
+
+    impl RobotsFetcher for FakeFetcher {
+        fn fetch(&self, robots_url: &Url) -> FetchOutcome {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(self.delay);
+            match robots_url.host_str() {
+                Some("a.example") => {
+                    FetchOutcome::Found("User-agent: *\nDisallow: /private".into())
+                }
+                Some("b.example") => FetchOutcome::Unavailable,
+                _ => FetchOutcome::Unreachable,
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[async_trait::async_trait]
+    impl nicebot::AsyncRobotsFetcher for FakeFetcher {
+        async fn fetch_async(&self, robots_url: &Url) -> FetchOutcome {
+            for _ in 0..100 {
+                futures_lite::future::yield_now().await;
+            }
+            self.fetch(robots_url)
+        }
+    }
+
+    /// Fetches robots.txt with a bare-bones HTTP/1.0 client.
+    struct LoopbackFetcher;
+
+    impl RobotsFetcher for LoopbackFetcher {
+        fn fetch(&self, robots_url: &Url) -> FetchOutcome {
+            let address = (robots_url.host_str().unwrap(), robots_url.port().unwrap());
+            let Ok(mut stream) = TcpStream::connect(address) else {
+                return FetchOutcome::Unreachable;
+            };
+            write!(stream, "GET {} HTTP/1.0\r\n\r\n", robots_url.path()).unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut status = String::new();
+            reader.read_line(&mut status).unwrap();
+            let mut response = String::new();
+            reader.read_to_string(&mut response).unwrap();
+            let body = response.split_once("\r\n\r\n").unwrap().1;
+            match status.split(' ').nth(1) {
+                Some("200") => FetchOutcome::Found(body.to_owned()),
+                Some(code) if code.starts_with('4') => FetchOutcome::Unavailable,
+                _ => FetchOutcome::Unreachable,
+            }
+        }
+    }
+
+    /// Serves `test-data/tor.txt` at `/robots.txt` and answers everything else with 404.
+    fn serve_loopback(requests: usize) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let response = if request.starts_with("GET /robots.txt ") {
+                    let body = std::fs::read_to_string("test-data/tor.txt").unwrap();
+                    format!(
+                        "HTTP/1.0 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                } else {
+                    "HTTP/1.0 404 Not Found\r\n\r\n".to_owned()
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn robots_location() {
+        let url = Url::parse("https://user:pw@a.example:8080/a/b?c=d#e").unwrap();
+
+        assert_eq!(
+            robots_url(&url).unwrap().as_str(),
+            "https://a.example:8080/robots.txt"
+        );
+    }
+
+    #[test]
+    fn outcomes() {
+        let meta = FetchingMetaBot::new(None, FakeFetcher::new(Duration::ZERO));
+
+        assert_eq!(
+            meta.check("https://a.example/private"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(
+            meta.check("https://a.example/public"),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(
+            meta.check("https://b.example/private"),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(
+            meta.check("https://c.example/public"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(meta.fetcher().fetches.load(Ordering::SeqCst), 3);
+
+        // Every outcome is kept until it expires, unreachable ones only for the retry TTL.
+        assert_eq!(
+            meta.check("https://c.example/public"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(
+            meta.check("https://a.example/public"),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(meta.fetcher().fetches.load(Ordering::SeqCst), 3);
+        assert_eq!(meta.bots().len(), 3);
+        let now = SystemTime::now();
+        let c = Host::parse("c.example").unwrap();
+        assert!(meta.bots().expires_at(&c).unwrap() <= now + DEFAULT_RETRY_TTL);
+        assert_eq!(meta.bots().stale_hosts(now + DEFAULT_RETRY_TTL), [c]);
+    }
+
+    #[test]
+    fn refetch_expired() {
+        let mut meta = FetchingMetaBot::new(None, FakeFetcher::new(Duration::ZERO));
+        meta.set_retry_ttl(Duration::ZERO);
+        let a = Host::parse("a.example").unwrap();
+
+        assert_eq!(
+            meta.check("https://a.example/private"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(meta.bots().is_stale(&a, SystemTime::now()), Some(false));
+        meta.bots()
+            .set_expiry(&a, UNIX_EPOCH, Duration::from_secs(60));
+        assert_eq!(
+            meta.check("https://a.example/private"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(meta.fetcher().fetches.load(Ordering::SeqCst), 2);
+
+        for _ in 0..2 {
+            assert_eq!(
+                meta.check("https://c.example/public"),
+                Ok(Permission::Denied)
+            );
+        }
+        assert_eq!(meta.fetcher().fetches.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn single_flight() {
+        let meta = FetchingMetaBot::new(None, FakeFetcher::new(Duration::from_millis(100)));
+
+        std::thread::scope(|scope| {
+            for _ in 0..64 {
+                scope.spawn(|| {
+                    assert_eq!(
+                        meta.check("https://a.example/private"),
+                        Ok(Permission::Denied)
+                    );
+                });
+            }
+        });
+
+        assert_eq!(meta.fetcher().fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn single_flight_async() {
+        use nicebot::CheckURLAsync;
+        use std::sync::Arc;
+
+        let meta = Arc::new(FetchingMetaBot::new(None, FakeFetcher::new(Duration::ZERO)));
+
+        let tasks: Vec<_> = (0..1000)
+            .map(|_| {
+                let meta = meta.clone();
+                tokio::spawn(async move { meta.check_async("https://a.example/private").await })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), Ok(Permission::Denied));
+        }
+
+        assert_eq!(meta.fetcher().fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn loopback() {
+        let port = serve_loopback(1);
+        let meta = FetchingMetaBot::new(None, LoopbackFetcher);
+
+        let base = format!("http://127.0.0.1:{port}");
+        assert_eq!(meta.check(format!("{base}/scss")), Ok(Permission::Denied));
+        assert_eq!(
+            meta.check(format!("{base}/sass")),
+            Ok(Permission::Unspecified)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(all(test, feature = "http"))]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        time::{Duration, SystemTime},
+    };
+
+    use nicebot::{CheckURL, FetchOutcome, HttpFetcher, MetaBot, Permission, RobotsFetcher};
+    use url::Url;
+
+    /// Serves `/robots.txt` from `test-data/<name>.txt`, redirecting `hops` times first.
+    /// `/slow/robots.txt` never answers, `/missing/robots.txt` and `/broken/robots.txt` answer with 404 and 503.
+    fn serve(name: &'static str, hops: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                let path = request.split(' ').nth(1).unwrap_or_default().to_owned();
+                // Skip the headers of the request.
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|read| read > 2) {
+                    line.clear();
+                }
+
+                let hop = match path.as_str() {
+                    "/robots.txt" => Some(0),
+                    _ => path.strip_prefix("/hop/").and_then(|n| n.parse().ok()),
+                };
+                let response = match (path.as_str(), hop) {
+                    ("/slow/robots.txt", _) => {
+                        std::thread::sleep(Duration::from_secs(2));
+                        continue;
+                    }
+                    ("/broken/robots.txt", _) => {
+                        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_owned()
+                    }
+                    (_, Some(hop)) if hop < hops => format!(
+                        "HTTP/1.1 301 Moved Permanently\r\nLocation: /hop/{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        hop + 1
+                    ),
+                    (_, Some(_)) => {
+                        let body = std::fs::read_to_string(format!("test-data/{name}.txt")).unwrap();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nCache-Control: max-age=60\r\nConnection: close\r\n\r\n{body}",
+                            body.len()
+                        )
+                    }
+                    _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_owned(),
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        base
+    }
+
+    fn robots(base: &str, prefix: &str) -> Url {
+        Url::parse(&format!("{base}{prefix}/robots.txt")).unwrap()
+    }
+
+    #[test]
+    fn outcomes() {
+        let base = serve("tor", 0);
+        let fetcher = HttpFetcher::with_limits("nicebot", 1024 * 1024, Duration::from_millis(500));
+
+        let tor = std::fs::read_to_string("test-data/tor.txt").unwrap();
+        assert_eq!(fetcher.fetch(&robots(&base, "")), FetchOutcome::Found(tor));
+        assert_eq!(
+            fetcher.fetch(&robots(&base, "/missing")),
+            FetchOutcome::Unavailable
+        );
+        assert_eq!(
+            fetcher.fetch(&robots(&base, "/broken")),
+            FetchOutcome::Unreachable
+        );
+        assert_eq!(
+            fetcher.fetch(&robots(&base, "/slow")),
+            FetchOutcome::Unreachable
+        );
+    }
+
+    #[test]
+    fn redirects() {
+        let fetcher = HttpFetcher::new("nicebot");
+
+        let base = serve("reddit", 5);
+        assert!(matches!(
+            fetcher.fetch(&robots(&base, "")),
+            FetchOutcome::Found(_)
+        ));
+
+        let base = serve("reddit", 6);
+        assert_eq!(fetcher.fetch(&robots(&base, "")), FetchOutcome::Unavailable);
+    }
+
+    #[test]
+    fn size_cap() {
+        let base = serve("wikipedia", 0);
+        let fetcher = HttpFetcher::with_limits("nicebot", 100, Duration::from_secs(5));
+
+        let FetchOutcome::Found(body) = fetcher.fetch(&robots(&base, "")) else {
+            panic!("robots.txt should have been found");
+        };
+        assert_eq!(body.len(), 100);
+    }
+
+    #[test]
+    fn load() {
+        let base = serve("tor", 0);
+        let fetcher = HttpFetcher::new("nicebot");
+        let mut meta = MetaBot::new(None);
+
+        let url = Url::parse(&format!("{base}/scss")).unwrap();
+        let before = SystemTime::now();
+        fetcher.load(&mut meta, &url).unwrap();
+
+        assert_eq!(meta.check(url.clone()), Ok(Permission::Denied));
+        let host = url.host().unwrap().to_owned();
+        let expires_at = meta.expires_at(&host).unwrap();
+        assert!(expires_at >= before + Duration::from_secs(60));
+        assert!(expires_at <= SystemTime::now() + Duration::from_secs(60));
+    }
+
+    #[cfg(feature = "http-async")]
+    #[tokio::test]
+    async fn load_async() {
+        let base = serve("tor", 0);
+        let fetcher = HttpFetcher::new("nicebot");
+        let mut meta = MetaBot::new(None);
+
+        let url = Url::parse(&format!("{base}/scss")).unwrap();
+        fetcher.load_async(&mut meta, &url).await.unwrap();
+
+        assert_eq!(meta.check(url), Ok(Permission::Denied));
+    }
+}
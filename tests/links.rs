@@ -0,0 +1,87 @@
+#[cfg(all(test, feature = "links"))]
+mod tests {
+    use nicebot::{LinkExtractor, MetaBot, TryAddRobots};
+    use url::Url;
+
+    const HTML: &str = r##"
+        <html><head><title>Links</title></head>
+        <body>
+        <a href="/public">Public</a>
+        <a href="/private/page#top">Private</a>
+        <a href="/public#again">Again</a>
+        <a href="https://other.example/ad" rel="sponsored noopener">Ad</a>
+        <a href="/comments" rel="UGC">Comments</a>
+        <a href="mailto:mail@example.com">Mail</a>
+        <a href="https://[::1">Broken</a>
+        <map><area href="/area?a=1&amp;b=2"></map>
+        <!-- <a href="/commented"> -->
+        </body></html>
+    "##;
+
+    fn meta() -> MetaBot {
+        let mut meta = MetaBot::new(Some("nicebot".to_owned()));
+        meta.try_add_robots("example.com", "User-agent: *\nDisallow: /private");
+        meta
+    }
+
+    fn urls(urls: &[Url]) -> Vec<&str> {
+        urls.iter().map(Url::as_str).collect()
+    }
+
+    #[test]
+    fn extract() {
+        let base = Url::parse("https://example.com/dir/page.html").unwrap();
+        let links = LinkExtractor::new().extract(&meta(), &base, HTML);
+
+        assert_eq!(
+            urls(&links.allowed),
+            vec![
+                "https://example.com/public",
+                "https://other.example/ad",
+                "https://example.com/comments",
+                "https://example.com/area?a=1&b=2",
+            ]
+        );
+        assert_eq!(
+            urls(&links.denied),
+            vec!["https://example.com/private/page"]
+        );
+        assert!(links.nofollow.is_empty());
+        assert_eq!(links.invalid, vec!["https://[::1"]);
+    }
+
+    #[test]
+    fn nofollow() {
+        let base = Url::parse("https://example.com/").unwrap();
+        let mut extractor = LinkExtractor::new();
+        extractor.set_skipped_rels(&["nofollow", "ugc", "sponsored"]);
+        let links = extractor.extract(&meta(), &base, HTML);
+        assert_eq!(
+            urls(&links.nofollow),
+            vec!["https://other.example/ad", "https://example.com/comments"]
+        );
+
+        let html = format!(r#"<meta name="nicebot" content="nofollow">{HTML}"#);
+        let links = extractor.extract(&meta(), &base, &html);
+        assert!(links.allowed.is_empty() && links.denied.is_empty());
+        assert_eq!(links.nofollow.len(), 5);
+
+        let links = extractor.extract_with_headers(&meta(), &base, HTML, &["nicebot: none"]);
+        assert_eq!(links.nofollow.len(), 5);
+
+        extractor.set_respect_meta_nofollow(false);
+        let links = extractor.extract(&meta(), &base, &html);
+        assert_eq!(links.allowed.len(), 2);
+    }
+
+    #[test]
+    fn base_tag() {
+        let html = r#"<base href="https://cdn.example/assets/"><a href="img.png">Image</a>"#;
+        let base = Url::parse("https://example.com/").unwrap();
+        let links = LinkExtractor::new().extract(&meta(), &base, html);
+        assert_eq!(
+            urls(&links.allowed),
+            vec!["https://cdn.example/assets/img.png"]
+        );
+    }
+}
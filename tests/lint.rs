@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use nicebot::{LintKind, ParseProfile, Permission, RobotsTxt, SingleBot, lint};
+
+    fn kinds(input: &str) -> Vec<(usize, LintKind, Option<usize>)> {
+        lint(&RobotsTxt::parse(input))
+            .into_iter()
+            .map(|finding| (finding.line, finding.kind, finding.related_line))
+            .collect()
+    }
+
+    #[test]
+    fn shadowed_rules() {
+        let input = "\
+User-agent: *
+Disallow: /a
+Allow: /a
+Disallow: /b
+Disallow: /b*
+Disallow: /c*d
+Allow: /cd
+Disallow: /e
+Disallow: /e/f
+";
+
+        assert_eq!(
+            kinds(input),
+            [
+                (2, LintKind::ShadowedRule, Some(3)),
+                (4, LintKind::ShadowedRule, Some(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_pattern_per_profile() {
+        let input = "User-agent: *\nAllow: /a\nDisallow: /a";
+        for profile in [
+            ParseProfile::Classic,
+            ParseProfile::Rfc9309,
+            ParseProfile::Google,
+            ParseProfile::Bing,
+            ParseProfile::Yandex,
+        ] {
+            let findings = lint(&RobotsTxt::parse_with_profile(input, profile));
+            let mut bot = SingleBot::with_profile(None, profile);
+            bot += input;
+            // The rule reported as overridden is the one the bot doesn't follow.
+            let (line, related) = match bot.check("/a") {
+                Permission::Allowed => (3, 2),
+                _ => (2, 3),
+            };
+            assert_eq!(findings.len(), 1, "{profile:?}");
+            assert_eq!(
+                (findings[0].kind, findings[0].line, findings[0].related_line),
+                (LintKind::ShadowedRule, line, Some(related)),
+                "{profile:?}"
+            );
+        }
+        assert_eq!(
+            lint(&RobotsTxt::parse_with_profile(input, ParseProfile::Rfc9309))[0].message,
+            "`Disallow: /a` is overridden by `Allow: /a` on line 2"
+        );
+    }
+
+    #[test]
+    fn blocked_assets() {
+        let input = "\
+User-agent: *
+Disallow: /assets/
+Allow: /assets/*.css$
+Disallow: /*.js$
+Disallow: /admin.php
+";
+
+        assert_eq!(
+            kinds(input),
+            [
+                (2, LintKind::BlocksAssets, None),
+                (4, LintKind::BlocksAssets, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn star_disallows_all() {
+        let input = "\
+User-agent: Googlebot
+Allow: /
+
+User-agent: BadBot
+Disallow: /
+
+User-agent: *
+Disallow: /
+";
+
+        assert_eq!(kinds(input), [(8, LintKind::StarDisallowsAll, Some(1))]);
+    }
+
+    #[test]
+    fn duplicates_and_dollar() {
+        let input = "\
+User-agent: Googlebot
+Disallow: /a$/b
+
+User-agent: *
+User-agent: googlebot
+Disallow: /c$
+";
+
+        let findings = lint(&RobotsTxt::parse_with_profile(input, ParseProfile::Rfc9309));
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].kind, LintKind::TextAfterDollar);
+        assert_eq!(findings[0].line, 2);
+        assert_eq!(findings[1].kind, LintKind::DuplicateGroup);
+        assert_eq!(findings[1].related_line, Some(1));
+        assert_eq!(
+            findings[1].to_string(),
+            "line 4: `googlebot` already has a group on line 1, parsers may use either or merge them"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+#[cfg(all(test, feature = "meta"))]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use nicebot::{
+        CheckURL, CheckURLAt, ExpiredFallback, MetaBot, ParseProfile, Permission, TryAddRobots,
+        ttl_from_headers,
+    };
+    use url::{Host, Url};
+
+    const ROBOTS: &str = r#"
+        User-agent: *
+        Disallow: /private
+    "#;
+
+    fn host(name: &str) -> Host<String> {
+        Host::parse(name).unwrap()
+    }
+
+    #[test]
+    fn stale_hosts() {
+        let mut meta = MetaBot::new(None);
+        meta.try_add_robots("a.example", ROBOTS);
+        meta.try_add_robots("b.example", ROBOTS);
+
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(1_000);
+        meta.set_expiry(&host("a.example"), fetched_at, Duration::from_secs(60));
+        meta.set_expiry(&host("b.example"), fetched_at, Duration::from_secs(600));
+
+        let now = fetched_at + Duration::from_secs(120);
+        let stale: Vec<_> = meta.stale_hosts(now).collect();
+
+        assert_eq!(stale, vec![&host("a.example")]);
+        assert_eq!(meta.is_stale(&host("b.example"), now), Some(false));
+        assert_eq!(meta.is_stale(&host("c.example"), now), None);
+    }
+
+    #[test]
+    fn expired_fallback() {
+        let mut meta = MetaBot::new(None);
+        meta.try_add_robots("a.example", ROBOTS);
+
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(1_000);
+        meta.set_expiry(&host("a.example"), fetched_at, Duration::from_secs(60));
+        let fresh = fetched_at + Duration::from_secs(30);
+        let expired = fetched_at + Duration::from_secs(90);
+
+        let url = "https://a.example/private";
+        assert_eq!(meta.check(url), Ok(Permission::Denied));
+        assert_eq!(meta.check_at(url, expired), Ok(Permission::Denied));
+
+        meta.set_expired_fallback(ExpiredFallback::Unspecified);
+        assert_eq!(meta.check_at(url, fresh), Ok(Permission::Denied));
+        assert_eq!(meta.check_at(url, expired), Ok(Permission::Unspecified));
+
+        meta.set_expired_fallback(ExpiredFallback::Denied);
+        assert_eq!(
+            meta.check_at("https://a.example/public", fresh),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(
+            meta.check_at("https://a.example/public", expired),
+            Ok(Permission::Denied)
+        );
+        // `check` uses the current time, long after the expiry.
+        assert_eq!(
+            meta.check("https://a.example/public"),
+            Ok(Permission::Denied)
+        );
+    }
+
+    #[test]
+    fn ttl_is_capped() {
+        let mut meta = MetaBot::new(None);
+        meta.set_ttl(Duration::from_secs(3_600));
+        meta.try_add_robots("a.example", ROBOTS);
+
+        let fetched_at = SystemTime::now();
+        meta.set_expiry_from_headers(&host("a.example"), fetched_at, Some("max-age=86400"), None);
+
+        assert_eq!(
+            meta.expires_at(&host("a.example")),
+            Some(fetched_at + Duration::from_secs(3_600))
+        );
+    }
+
+    #[test]
+    fn ttl_headers() {
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(784_111_777); // Sun, 06 Nov 1994 08:49:37 GMT
+
+        assert_eq!(
+            ttl_from_headers(Some("public, max-age=300"), None, fetched_at),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            ttl_from_headers(Some("max-age=300, s-maxage=60"), None, fetched_at),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            ttl_from_headers(Some("no-cache"), None, fetched_at),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            ttl_from_headers(None, Some("Sun, 06 Nov 1994 09:49:37 GMT"), fetched_at),
+            Some(Duration::from_secs(3_600))
+        );
+        assert_eq!(
+            ttl_from_headers(None, Some("Sunday, 06-Nov-94 08:50:37 GMT"), fetched_at),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            ttl_from_headers(None, Some("0"), fetched_at),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(ttl_from_headers(Some("public"), None, fetched_at), None);
+        assert_eq!(
+            ttl_from_headers(
+                None,
+                Some("Sun, 06 Nov 999999999999 08:49:37 GMT"),
+                fetched_at
+            ),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn sharing() {
+        let mut meta = MetaBot::new(None);
+        for name in ["a.com", "b.com", "c.com"] {
+            meta.try_add_robots(name, ROBOTS);
+        }
+        meta.try_add_robots("d.com", "User-agent: *\nDisallow: /");
+
+        let mut single = MetaBot::new(None);
+        single.try_add_robots("a.com", ROBOTS);
+        assert!(meta.approximate_size() < 3 * single.approximate_size());
+
+        let stats = meta.sharing_stats();
+        assert_eq!(stats.hosts, 4);
+        assert_eq!(stats.distinct_bots, 2);
+        assert_eq!(stats.shared_hosts, 3);
+        assert!(stats.saved_bytes > 0);
+        assert_eq!(stats.sharing_ratio(), 2.0);
+        assert!(std::ptr::eq(
+            meta.get(&host("a.com")).unwrap().bot(),
+            meta.get(&host("c.com")).unwrap().bot()
+        ));
+
+        // Extending the robots.txt of one host leaves the others untouched.
+        meta.try_add_robots("a.com", "User-agent: *\nDisallow: /extra");
+        assert_eq!(
+            meta.check("https://a.com/extra").unwrap(),
+            Permission::Denied
+        );
+        assert_eq!(
+            meta.check("https://b.com/extra").unwrap(),
+            Permission::Unspecified
+        );
+        assert_eq!(meta.sharing_stats().distinct_bots, 3);
+
+        // Clones share their bots, but count their hosts on their own.
+        let mut copy = meta.clone();
+        assert_eq!(copy.sharing_stats(), meta.sharing_stats());
+        copy.remove(&host("b.com"));
+        copy.remove(&host("c.com"));
+        assert_eq!(copy.sharing_stats().shared_hosts, 0);
+        assert_eq!(meta.sharing_stats().shared_hosts, 2);
+
+        meta.remove(&host("d.com"));
+        meta.remove(&host("a.com"));
+        meta.shrink();
+        let stats = meta.sharing_stats();
+        assert_eq!((stats.hosts, stats.distinct_bots), (2, 1));
+        assert_eq!(
+            meta.check("https://b.com/private").unwrap(),
+            Permission::Denied
+        );
+
+        meta.remove(&host("b.com"));
+        meta.remove(&host("c.com"));
+        assert_eq!(meta.approximate_size(), 0);
+    }
+
+    #[test]
+    fn check_as() {
+        let mut meta = MetaBot::new(Some("nicebot".to_owned()));
+        meta.try_add_robots(
+            "a.example",
+            "User-agent: *\nDisallow: /private\n\nUser-agent: GPTBot\nDisallow: /",
+        );
+
+        let url = Url::parse("https://a.example/public").unwrap();
+        assert_eq!(meta.check(url.as_str()), Ok(Permission::Unspecified));
+        assert_eq!(meta.check_as("GPTBot", &url), Ok(Permission::Denied));
+        assert_eq!(meta.check_as("nicebot", &url), Ok(Permission::Unspecified));
+        assert_eq!(
+            meta.check_as("gptbot", &Url::parse("https://b.example/").unwrap()),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(meta.content_signals(&host("a.example")), None);
+        assert_eq!(
+            meta.get_as(&host("a.example"), "GPTBot")
+                .map(|view| view.check("/public")),
+            Some(Permission::Denied)
+        );
+        assert!(meta.get_as(&host("b.example"), "GPTBot").is_none());
+        assert_eq!(meta.hosts().collect::<Vec<_>>(), vec![&host("a.example")]);
+
+        meta.try_add_robots(
+            "a.example",
+            "User-agent: nicebot\nContent-Signal: ai-input=no",
+        );
+        let signals = meta.content_signals(&host("a.example")).unwrap();
+        assert_eq!(signals.ai_input, Some(false));
+    }
+
+    #[test]
+    fn profile() {
+        let robots_txt = "User-agent: Googlebot/2.1\nDissallow: /private";
+        let url = "https://a.example/private";
+
+        let mut meta = MetaBot::new(Some("googlebot".to_owned()));
+        meta.try_add_robots("a.example", robots_txt);
+        assert_eq!(meta.check(url), Ok(Permission::Unspecified));
+
+        let mut meta = MetaBot::with_profile(Some("googlebot".to_owned()), ParseProfile::Google);
+        meta.try_add_robots("a.example", robots_txt);
+        assert_eq!(meta.check(url), Ok(Permission::Denied));
+        assert_eq!(meta.profile(), ParseProfile::Google);
+    }
+
+    #[test]
+    fn canonicalise() {
+        let robots_txt = "User-agent: Yandex\nDisallow: /private\nClean-param: utm_source&ref /articles/\nHost: https://www.example.com";
+        let mut meta = MetaBot::with_profile(Some("yandexbot".to_owned()), ParseProfile::Yandex);
+        meta.try_add_robots("example.com", robots_txt);
+        meta.try_add_robots("www.example.com", robots_txt);
+
+        let url =
+            Url::parse("http://example.com/articles/1?utm_source=feed&page=2&ref=x#top").unwrap();
+        let canonical = meta.canonicalise(&url).unwrap();
+        assert_eq!(
+            canonical.url.as_str(),
+            "http://example.com/articles/1?page=2#top"
+        );
+        assert_eq!(
+            canonical.preferred().as_str(),
+            "https://www.example.com/articles/1?page=2#top"
+        );
+
+        let url = Url::parse("https://www.example.com/other?ref=x").unwrap();
+        let canonical = meta.canonicalise(&url).unwrap();
+        assert_eq!(canonical.url, url);
+        assert_eq!(canonical.mirror, None);
+
+        let unknown = Url::parse("https://unknown.example/articles/?ref=x").unwrap();
+        assert_eq!(meta.canonicalise(&unknown).unwrap().url, unknown);
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nicebot::{ParseProfile, Permission, SingleBot};
+
+    fn bot(profile: ParseProfile, user_agent: &str, robots_txt: &str) -> SingleBot {
+        let mut bot = SingleBot::with_profile(Some(user_agent.to_owned()), profile);
+        bot += robots_txt;
+        bot
+    }
+
+    #[test]
+    fn typos() {
+        let robots_txt =
+            "Useragent: *\nDissallow: /private\nSite-map: https://example.com/sitemap.xml";
+
+        let google = bot(ParseProfile::Google, "googlebot", robots_txt);
+        assert_eq!(google.check("/private"), Permission::Denied);
+        assert_eq!(google.sitemaps(), ["https://example.com/sitemap.xml"]);
+
+        let strict = bot(ParseProfile::Rfc9309, "googlebot", robots_txt);
+        assert_eq!(strict.check("/private"), Permission::Unspecified);
+        assert!(strict.sitemaps().is_empty());
+    }
+
+    #[test]
+    fn groups() {
+        let robots_txt =
+            "Disallow: /leading\n\nUser-agent: ExampleBot\nUser-agent: OtherBot\nDisallow: /shared";
+
+        let classic = bot(ParseProfile::Classic, "examplebot", robots_txt);
+        assert_eq!(classic.check("/shared"), Permission::Unspecified);
+        let strict = bot(ParseProfile::Rfc9309, "ExampleBot", robots_txt);
+        assert_eq!(strict.check("/shared"), Permission::Denied);
+        assert_eq!(strict.check("/leading"), Permission::Unspecified);
+
+        let bing = bot(ParseProfile::Bing, "bingbot", robots_txt);
+        assert_eq!(bing.check("/leading"), Permission::Denied);
+        assert_eq!(bing.check("/shared"), Permission::Unspecified);
+    }
+
+    #[test]
+    fn user_agents() {
+        let robots_txt = "User-agent: Googlebot/2.1\nDisallow: /a\n\nUser-agent: Googlebot-News\nDisallow: /b\n\nUser-agent: Yandex\nDisallow: /c";
+
+        let google = bot(ParseProfile::Google, "Googlebot", robots_txt);
+        assert_eq!(google.check("/a"), Permission::Denied);
+        assert_eq!(google.check("/b"), Permission::Unspecified);
+
+        let classic = bot(ParseProfile::Classic, "googlebot", robots_txt);
+        assert_eq!(classic.check("/a"), Permission::Denied);
+        assert_eq!(classic.check("/b"), Permission::Denied);
+
+        let strict = bot(ParseProfile::Rfc9309, "googlebot", robots_txt);
+        assert_eq!(strict.check("/a"), Permission::Unspecified);
+
+        let yandex = bot(ParseProfile::Yandex, "YandexBot", robots_txt);
+        assert_eq!(yandex.check("/c"), Permission::Denied);
+        let bing = bot(ParseProfile::Bing, "YandexBot", robots_txt);
+        assert_eq!(bing.check("/c"), Permission::Unspecified);
+    }
+
+    #[test]
+    fn patterns() {
+        let robots_txt =
+            "User-agent: *\nDisallow:\nDisallow: private\nAllow: /page\nDisallow: /page";
+
+        let classic = bot(ParseProfile::Classic, "nicebot", robots_txt);
+        assert_eq!(classic.check("/public"), Permission::Denied);
+        assert_eq!(classic.check("/private"), Permission::Denied);
+        assert_eq!(classic.check("/page"), Permission::Denied);
+
+        let google = bot(ParseProfile::Google, "nicebot", robots_txt);
+        assert_eq!(google.check("/public"), Permission::Unspecified);
+        assert_eq!(google.check("/private"), Permission::Denied);
+        assert_eq!(google.check("/page"), Permission::Allowed);
+
+        let strict = bot(ParseProfile::Rfc9309, "nicebot", robots_txt);
+        assert_eq!(strict.check("/private"), Permission::Unspecified);
+        assert_eq!(strict.check("/page"), Permission::Allowed);
+    }
+
+    #[test]
+    fn index_files() {
+        let robots_txt = "User-agent: *\nDisallow: /index.html\nDisallow: /docs/$";
+
+        let yandex = bot(ParseProfile::Yandex, "yandexbot", robots_txt);
+        assert_eq!(yandex.check("/"), Permission::Denied);
+        assert_eq!(yandex.check("/docs/index.html"), Permission::Denied);
+        assert_eq!(
+            yandex.explain("/docs/index.htm").rule.as_deref(),
+            Some("/docs/$")
+        );
+        assert_eq!(yandex.check("/docs/page.html"), Permission::Unspecified);
+
+        let google = bot(ParseProfile::Google, "googlebot", robots_txt);
+        assert_eq!(google.check("/"), Permission::Unspecified);
+        assert_eq!(google.check("/docs/index.html"), Permission::Unspecified);
+    }
+
+    #[test]
+    fn crawl_delay() {
+        let robots_txt = "User-agent: *\nCrawl-delay: 5\nRequest-rate: 1/10";
+
+        let bing = bot(ParseProfile::Bing, "bingbot", robots_txt);
+        assert_eq!(bing.crawl_delay(), Some(Duration::from_secs(5)));
+        assert_eq!(bing.request_rate(), None);
+
+        let google = bot(ParseProfile::Google, "googlebot", robots_txt);
+        assert_eq!(google.crawl_delay(), None);
+        assert_eq!(google.profile(), ParseProfile::Google);
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use nicebot::{AgentBot, Correction, Permission, Recogniser, SingleBot, SpacePolicy};
+
+    #[test]
+    fn typos() {
+        let strict = Recogniser::strict();
+        let lenient = Recogniser::lenient();
+
+        assert_eq!(strict.recognise("Dissallow: /private"), None);
+        let line = lenient.recognise("Dissallow: /private").unwrap();
+        assert_eq!(line.directive, "disallow");
+        assert_eq!(line.value, "/private");
+        assert_eq!(line.corrections, [Correction::Typo("dissallow".to_owned())]);
+
+        for written in ["User agent: *", "useragent: *", "User  Agent :*"] {
+            let line = lenient.recognise(written).unwrap();
+            assert_eq!(line.directive, "user-agent", "{written}");
+            assert_eq!(line.value, "*", "{written}");
+        }
+
+        let line = strict.recognise("Allow : /public").unwrap();
+        assert_eq!(line.directive, "allow");
+        assert!(line.corrections.is_empty());
+        assert_eq!(lenient.recognise("Noindex: /foo"), None);
+    }
+
+    #[test]
+    fn missing_colon() {
+        assert_eq!(Recogniser::strict().recognise("Disallow /private"), None);
+
+        let line = Recogniser::lenient().recognise("Disallow /a:b").unwrap();
+        assert_eq!(line.directive, "disallow");
+        assert_eq!(line.value, "/a:b");
+        assert_eq!(line.corrections, [Correction::MissingColon]);
+    }
+
+    #[test]
+    fn spaces() {
+        let recogniser = |spaces| Recogniser {
+            spaces,
+            ..Recogniser::strict()
+        };
+
+        assert_eq!(
+            recogniser(SpacePolicy::Ignore).recognise("Disallow: /my page"),
+            None
+        );
+        let kept = recogniser(SpacePolicy::Keep)
+            .recognise("Disallow: /my page")
+            .unwrap();
+        assert_eq!(kept.value, "/my page");
+        assert_eq!(kept.corrections, [Correction::Spaces]);
+        let first = recogniser(SpacePolicy::FirstWord)
+            .recognise("Disallow: /my page")
+            .unwrap();
+        assert_eq!(first.value, "/my");
+        assert_eq!(
+            first.corrections,
+            [Correction::Truncated("/my page".to_owned())]
+        );
+
+        let signal = Recogniser::strict()
+            .recognise("Content-Signal: search=yes, ai-train=no")
+            .unwrap();
+        assert_eq!(signal.value, "search=yes, ai-train=no");
+        assert!(signal.corrections.is_empty());
+    }
+
+    #[test]
+    fn bots() {
+        let robots_txt = "User agent: *\nDisallow /private\nDisallow: /my page";
+        let recogniser = Recogniser {
+            spaces: SpacePolicy::FirstWord,
+            ..Recogniser::lenient()
+        };
+
+        let mut single = SingleBot::new(Some("examplebot".to_owned()));
+        single.set_recogniser(recogniser);
+        single += robots_txt;
+        let mut agents = AgentBot::new();
+        agents.set_recogniser(recogniser);
+        agents += robots_txt;
+
+        for path in ["/private", "/my"] {
+            assert_eq!(single.check(path), Permission::Denied, "{path}");
+            assert_eq!(
+                agents.check("examplebot", path),
+                Permission::Denied,
+                "{path}"
+            );
+        }
+
+        let mut strict = SingleBot::new(Some("examplebot".to_owned()));
+        strict += robots_txt;
+        assert_eq!(strict.check("/private"), Permission::Unspecified);
+    }
+}
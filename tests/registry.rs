@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use nicebot::{AgentBot, Crawler, CrawlerCategory, CrawlerRegistry, Permission};
+
+    #[test]
+    fn builtin() {
+        let registry = CrawlerRegistry::builtin();
+
+        let gptbot = registry.get("gptbot").unwrap();
+        assert_eq!(gptbot.token, "GPTBot");
+        assert_eq!(gptbot.organisation, "OpenAI");
+        assert_eq!(gptbot.category, CrawlerCategory::AiTraining);
+        assert!(
+            registry
+                .by_category(CrawlerCategory::Search)
+                .any(|crawler| crawler.token == "Googlebot")
+        );
+        assert!(registry.iter().all(|crawler| !crawler.token.contains(' ')));
+
+        let mut bot = gptbot.bot();
+        bot += "User-agent: GPTBot\nDisallow: /";
+        assert_eq!(bot.check("/"), Permission::Denied);
+    }
+
+    #[test]
+    fn update() {
+        let mut registry = CrawlerRegistry::new();
+        registry
+            .add_list(
+                "# token, organisation, category\nExampleBot, Example, search\n\nexamplebot, Example Inc., AI-Training # renamed\n",
+            )
+            .unwrap();
+        assert_eq!(registry.len(), 1);
+        assert_eq!(
+            registry.get("ExampleBot"),
+            Some(&Crawler::new(
+                "examplebot",
+                "Example Inc.",
+                CrawlerCategory::AiTraining
+            ))
+        );
+
+        let err = registry.add_list("OtherBot, Other\n").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(registry.add_list("Other Bot, Other, search").is_err());
+        assert!(registry.add_list("OtherBot, Other, spam").is_err());
+
+        assert!(registry.remove("EXAMPLEBOT"));
+        assert!(registry.is_empty());
+        assert_eq!(CrawlerCategory::SocialPreview.to_string(), "social-preview");
+        assert!(CrawlerCategory::AiAssistant.is_ai());
+    }
+
+    #[test]
+    fn blocked() {
+        let bot = AgentBot::from(
+            r"
+            User-agent: *
+            Disallow: /private
+
+            User-agent: GPTBot
+            Disallow: /
+
+            User-agent: CCBot
+            Disallow: /
+            ",
+        );
+        let registry = CrawlerRegistry::builtin();
+
+        let blocked: Vec<_> = registry
+            .blocked(&bot)
+            .into_iter()
+            .map(|crawler| crawler.token.as_str())
+            .collect();
+        assert_eq!(blocked, vec!["GPTBot", "CCBot"]);
+
+        let private = registry.check(&bot, "/private");
+        assert!(
+            private
+                .iter()
+                .all(|(_, permission)| *permission == Permission::Denied)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(all(test, feature = "meta"))]
+mod tests {
+    use std::time::Duration;
+
+    use nicebot::{Access, AgentBot, MetaBot, PolicyReport, TryAddRobots, json_string};
+
+    const EXAMPLE: &str = r"
+        User-agent: *
+        Content-Signal: search=yes, ai-train=no
+        Disallow: /
+        Allow: /public
+
+        User-agent: Googlebot
+        Allow: /
+    ";
+
+    fn meta() -> MetaBot {
+        let mut meta = MetaBot::new(None);
+        for (host, file) in [("www.facebook.com", "facebook"), ("www.fsf.org", "fsf")] {
+            let robots = std::fs::read_to_string(format!("test-data/{file}.txt")).unwrap();
+            meta.try_add_robots(host, robots);
+        }
+        meta.try_add_robots("example.com", EXAMPLE);
+        meta
+    }
+
+    #[test]
+    fn access() {
+        let bot = AgentBot::from(EXAMPLE);
+        assert_eq!(
+            Access::of(&bot.for_agent(Some("gptbot"))),
+            Access::PartiallyBlocked
+        );
+        assert_eq!(
+            Access::of(&bot.for_agent(Some("googlebot"))),
+            Access::Allowed
+        );
+        assert_eq!(
+            Access::of(&AgentBot::deny_all().for_agent(None)),
+            Access::Blocked
+        );
+    }
+
+    #[test]
+    fn report() {
+        let report = PolicyReport::new(&meta(), ["GPTBot", "Googlebot"]);
+        assert_eq!(report.entries.len(), 6);
+
+        let gptbot: Vec<_> = report
+            .for_agent("gptbot")
+            .map(|entry| (entry.host.to_string(), entry.access))
+            .collect();
+        assert_eq!(
+            gptbot,
+            vec![
+                ("example.com".to_owned(), Access::PartiallyBlocked),
+                ("www.facebook.com".to_owned(), Access::Blocked),
+                ("www.fsf.org".to_owned(), Access::PartiallyBlocked),
+            ]
+        );
+
+        let fsf = report
+            .entries
+            .iter()
+            .find(|entry| entry.host.to_string() == "www.fsf.org")
+            .unwrap();
+        assert_eq!(fsf.crawl_delay, Some(Duration::from_secs(10)));
+
+        let example = &report.entries[0];
+        assert_eq!(example.content_signals.unwrap().ai_train, Some(false));
+    }
+
+    #[test]
+    fn export() {
+        let mut meta = MetaBot::new(None);
+        meta.try_add_robots("example.com", EXAMPLE);
+        let report = PolicyReport::new(&meta, ["GPTBot", "Googlebot"]);
+
+        assert_eq!(
+            report.to_csv(),
+            "host,agent,access,crawl_delay,content_signal\n\
+             example.com,GPTBot,partially-blocked,,\"search=yes, ai-train=no\"\n\
+             example.com,Googlebot,allowed,,\n"
+        );
+        assert_eq!(
+            report.to_json(),
+            r#"[{"host":"example.com","agent":"GPTBot","access":"partially-blocked","crawl_delay":null,"content_signal":{"search":true,"ai-input":null,"ai-train":false}},{"host":"example.com","agent":"Googlebot","access":"allowed","crawl_delay":null,"content_signal":null}]"#
+        );
+    }
+
+    #[test]
+    fn json_strings() {
+        assert_eq!(json_string("a \"b\"\\\n\u{1}é"), r#""a \"b\"\\\n\u0001é""#);
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use nicebot::{ImagePreview, IndexingDirectives, PageRobots};
+
+    #[test]
+    fn parse_directives() {
+        let directives = IndexingDirectives::parse(
+            "NOINDEX, nofollow, max-snippet:20, max-snippet:-1, max-image-preview:standard, unknown",
+        );
+
+        assert!(directives.noindex);
+        assert!(directives.nofollow);
+        assert!(!directives.nosnippet);
+        assert_eq!(directives.max_snippet, Some(20));
+        assert_eq!(directives.max_image_preview, Some(ImagePreview::Standard));
+        assert_eq!(directives.max_video_preview, None);
+
+        let none = IndexingDirectives::parse("none");
+        assert!(none.noindex && none.nofollow);
+        assert_eq!(
+            IndexingDirectives::parse("all"),
+            IndexingDirectives::default()
+        );
+    }
+
+    #[test]
+    fn unavailable_after() {
+        let expected = Some(UNIX_EPOCH + Duration::from_secs(1_635_951_600));
+        for date in [
+            "2021-11-03T15:00:00Z",
+            "2021-11-03T16:00:00+01:00",
+            "Wednesday, 03-Nov-2021 15:00:00 GMT",
+            "03 Nov 2021 15:00:00 GMT",
+        ] {
+            let directives = IndexingDirectives::parse(&format!("unavailable_after: {date}"));
+            assert_eq!(directives.unavailable_after, expected, "{date}");
+        }
+
+        let directives = IndexingDirectives::parse("unavailable_after: 2021-11-03");
+        let date = UNIX_EPOCH + Duration::from_secs(1_635_897_600);
+        assert!(directives.allows_indexing(date - Duration::from_secs(1)));
+        assert!(!directives.allows_indexing(date));
+
+        for date in [
+            "9999999999999999-01-01",
+            "2021-11-03T9999999999999999:00",
+            "2021-11-03T15:00:00+9999999999999999:00",
+        ] {
+            let directives = IndexingDirectives::parse(&format!("unavailable_after: {date}"));
+            assert_eq!(directives.unavailable_after, None, "{date}");
+        }
+        let mut page = PageRobots::new(None);
+        page.add_header("unavailable_after: 9999999999999999-01-01");
+        assert_eq!(page.directives().unavailable_after, None);
+    }
+
+    #[test]
+    fn headers() {
+        let mut page = PageRobots::new(Some("Googlebot".to_owned()));
+        page.add_header("noarchive");
+        assert!(page.directives().noarchive);
+
+        page.add_header("otherbot: noindex, nofollow");
+        page.add_header(
+            "googlebot: nosnippet, unavailable_after: Wednesday, 03-Nov-2021 15:00:00 GMT",
+        );
+        let directives = page.directives();
+        assert!(directives.nosnippet);
+        assert!(directives.unavailable_after.is_some());
+        assert!(!directives.noindex);
+        // Directives naming the user agent replace the generic ones.
+        assert!(!directives.noarchive);
+
+        let mut generic = PageRobots::new(None);
+        generic.add_header("googlebot: noindex");
+        generic.add_header("max-video-preview: 10");
+        assert!(!generic.directives().noindex);
+        assert_eq!(generic.directives().max_video_preview, Some(10));
+    }
+
+    #[test]
+    fn meta_tags() {
+        let html = r#"
+            <!DOCTYPE html>
+            <html><head>
+            <meta name="viewport" content="width=device-width">
+            <META NAME="robots" CONTENT="noindex">
+            <!-- <meta name="robots" content="nofollow"> -->
+            <script>document.write('<meta name="robots" content="noarchive">')</script>
+            <meta content='max-image-preview:large' name=bingbot />
+            </head></html>
+        "#;
+
+        let mut googlebot = PageRobots::new(Some("googlebot".to_owned()));
+        googlebot.add_html(html);
+        let directives = googlebot.directives();
+        assert!(directives.noindex);
+        assert!(!directives.nofollow);
+        assert!(!directives.noarchive);
+
+        let mut bingbot = PageRobots::new(Some("bingbot".to_owned()));
+        bingbot.add_html(html);
+        assert!(!bingbot.directives().noindex);
+        assert_eq!(
+            bingbot.directives().max_image_preview,
+            Some(ImagePreview::Large)
+        );
+
+        let mut viewport = PageRobots::new(Some("view".to_owned()));
+        viewport.add_html(html);
+        assert!(viewport.directives().noindex);
+    }
+}
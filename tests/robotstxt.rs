@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nicebot::{ParseProfile, Permission, RobotsTxt, Rule, SingleBot};
+
+    const INPUT: &str = r"
+Allow: /ignored
+User-agent: Googlebot
+User-agent: bingbot
+Disallow: /private
+Crawl-delay: 3
+
+User-agent: *
+Allow: /private/press # comment
+Unknown: /x
+Disallow: /with space
+User-agent: googlebot-news
+Disallow: /news
+Sitemap: https://example.com/sitemap.xml
+";
+
+    #[test]
+    fn groups() {
+        let robots = RobotsTxt::parse(INPUT);
+
+        // Like `SingleBot`, the default profile only applies the last of consecutive `User-agent` lines.
+        assert_eq!(robots.groups.len(), 4);
+        assert_eq!(robots.groups[0].user_agents, ["Googlebot"]);
+        assert_eq!(robots.groups[0].line, 3);
+        assert!(robots.groups[0].rules.is_empty());
+        assert_eq!(robots.groups[1].user_agents, ["bingbot"]);
+        assert_eq!(
+            robots.groups[1].rules,
+            [Rule {
+                permission: Permission::Denied,
+                pattern: "/private".into(),
+                line: 5,
+            }]
+        );
+        assert_eq!(robots.groups[1].crawl_delay, Some(Duration::from_secs(3)));
+        assert!(robots.groups[2].is_star());
+        assert_eq!(
+            robots.groups[2].rules[0].to_string(),
+            "Allow: /private/press"
+        );
+        assert_eq!(robots.groups[2].rules.len(), 1);
+        assert_eq!(robots.sitemaps, ["https://example.com/sitemap.xml"]);
+    }
+
+    #[test]
+    fn groups_for() {
+        let robots = RobotsTxt::parse(INPUT);
+
+        let lines = |agent| {
+            robots
+                .groups_for(agent)
+                .iter()
+                .map(|group| group.line)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(lines(Some("GoogleBot")), [3, 12]);
+        assert_eq!(lines(Some("bingbot")), [4]);
+        assert_eq!(lines(Some("examplebot")), [8]);
+        assert_eq!(lines(None), [8]);
+    }
+
+    #[test]
+    fn grouping() {
+        const CONSECUTIVE: &str = "User-agent: a\nUser-agent: b\nDisallow: /x";
+
+        let merged = RobotsTxt::parse_with_profile(CONSECUTIVE, ParseProfile::Rfc9309);
+        assert_eq!(merged.groups.len(), 1);
+        assert_eq!(merged.bot(Some("a")).check("/x"), Permission::Denied);
+
+        let classic = RobotsTxt::parse(CONSECUTIVE);
+        assert_eq!(classic.groups.len(), 2);
+        assert_eq!(classic.groups[1].line, 2);
+        for agent in ["a", "b"] {
+            let mut bot = SingleBot::new(Some(agent.to_owned()));
+            bot += CONSECUTIVE;
+            assert_eq!(classic.bot(Some(agent)).check("/x"), bot.check("/x"));
+        }
+    }
+
+    #[test]
+    fn profiles() {
+        let rfc = RobotsTxt::parse_with_profile(INPUT, ParseProfile::Rfc9309);
+        assert_eq!(rfc.groups.len(), 3);
+        assert_eq!(rfc.groups[0].user_agents, ["Googlebot", "bingbot"]);
+        assert_eq!(rfc.groups[0].crawl_delay, None);
+        let lines = |robots: &RobotsTxt, agent| {
+            robots
+                .groups_for(agent)
+                .iter()
+                .map(|group| group.line)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lines(&rfc, Some("GoogleBot")), [3]);
+
+        let bing = RobotsTxt::parse_with_profile(INPUT, ParseProfile::Bing);
+        assert_eq!(lines(&bing, None), [2, 8]);
+        assert_eq!(bing.bot(None).check("/ignored"), Permission::Allowed);
+
+        // Any other directive ends a run of `User-agent` lines, like for the bots.
+        const SPLIT: &str =
+            "User-agent: a\nSitemap: https://example.com/s.xml\nUser-agent: b\nDisallow: /x";
+        for profile in [ParseProfile::Classic, ParseProfile::Rfc9309] {
+            let robots = RobotsTxt::parse_with_profile(SPLIT, profile);
+            let mut bot = SingleBot::with_profile(Some("a".to_owned()), profile);
+            bot += SPLIT;
+            assert_eq!(robots.groups.len(), 2);
+            assert_eq!(robots.bot(Some("a")).check("/x"), bot.check("/x"));
+        }
+
+        let yandex = RobotsTxt::parse_with_profile(
+            "User-agent: *\nHost: example.com\nClean-param: ref /a/",
+            ParseProfile::Yandex,
+        );
+        let bot = yandex.bot(None);
+        assert_eq!(bot.host(), Some("example.com"));
+        assert_eq!(bot.clean_path("/a/1?ref=x&p=2"), "/a/1?p=2");
+    }
+}
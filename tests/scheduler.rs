@@ -0,0 +1,211 @@
+#[cfg(all(test, feature = "meta"))]
+mod tests {
+    use std::{
+        cell::Cell,
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+
+    use nicebot::{Clock, CrawlScheduler, MetaBot, Permission, TryAddRobots};
+    use url::{Host, Url};
+
+    #[derive(Clone)]
+    struct FakeClock(Rc<Cell<Instant>>);
+
+    impl FakeClock {
+        fn advance(&self, by: Duration) {
+            self.0.set(self.0.get() + by);
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            self.0.get()
+        }
+    }
+
+    fn scheduler() -> (CrawlScheduler<FakeClock>, FakeClock) {
+        let mut meta = MetaBot::new(None);
+        meta.try_add_robots(
+            "slow.example",
+            "User-agent: *\nCrawl-delay: 10\nDisallow: /private",
+        );
+        meta.try_add_robots("rate.example", "User-agent: *\nRequest-rate: 1/30s");
+        let clock = FakeClock(Rc::new(Cell::new(Instant::now())));
+        (CrawlScheduler::with_clock(meta, clock.clone()), clock)
+    }
+
+    fn host(name: &str) -> Host<String> {
+        Host::parse(name).unwrap()
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn delays() {
+        let (mut scheduler, clock) = scheduler();
+        scheduler.set_default_delay(Duration::from_secs(2));
+
+        assert_eq!(
+            scheduler.delay(&host("slow.example")),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            scheduler.delay(&host("rate.example")),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            scheduler.delay(&host("other.example")),
+            Duration::from_secs(2)
+        );
+
+        let start = clock.now();
+        assert_eq!(scheduler.next_allowed(&host("slow.example")), start);
+
+        scheduler.record_access(&host("slow.example"));
+        assert!(!scheduler.is_ready(&host("slow.example")));
+        assert_eq!(
+            scheduler.next_allowed(&host("slow.example")),
+            start + Duration::from_secs(10)
+        );
+
+        clock.advance(Duration::from_secs(10));
+        assert!(scheduler.is_ready(&host("slow.example")));
+    }
+
+    #[test]
+    fn backoff() {
+        let (mut scheduler, clock) = scheduler();
+        let slow = host("slow.example");
+
+        scheduler.record_access(&slow);
+        scheduler.record_response(&slow, 503, None);
+        assert_eq!(scheduler.delay(&slow), Duration::from_secs(20));
+
+        scheduler.record_response(&slow, 429, None);
+        assert_eq!(scheduler.delay(&slow), Duration::from_secs(40));
+
+        scheduler.record_response(&slow, 429, Some(Duration::from_secs(120)));
+        assert_eq!(
+            scheduler.next_allowed(&slow),
+            clock.now() + Duration::from_secs(120)
+        );
+
+        scheduler.record_response(&slow, 200, None);
+        assert_eq!(scheduler.delay(&slow), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn huge_delays() {
+        let mut meta = MetaBot::new(None);
+        meta.try_add_robots("delay.example", "User-agent: *\nCrawl-delay: 1e19");
+        meta.try_add_robots(
+            "rate.example",
+            "User-agent: *\nRequest-rate: 1/18446744073709551615",
+        );
+        let clock = FakeClock(Rc::new(Cell::new(Instant::now())));
+        let mut scheduler = CrawlScheduler::with_clock(meta, clock);
+        scheduler.set_max_backoff(Duration::MAX);
+
+        assert_eq!(
+            scheduler.delay(&host("delay.example")),
+            Duration::from_secs(24 * 60 * 60)
+        );
+        for name in ["delay.example", "rate.example"] {
+            let host = host(name);
+            scheduler.record_access(&host);
+            scheduler.record_response(&host, 503, Some(Duration::MAX));
+            assert!(!scheduler.is_ready(&host), "{name}");
+        }
+    }
+
+    #[test]
+    fn queue() {
+        let (mut scheduler, clock) = scheduler();
+
+        assert_eq!(
+            scheduler.push(url("https://slow.example/private")),
+            Ok(Permission::Denied)
+        );
+        for page in ["a", "b"] {
+            scheduler
+                .push(url(&format!("https://slow.example/{page}")))
+                .unwrap();
+            scheduler
+                .push(url(&format!("https://rate.example/{page}")))
+                .unwrap();
+        }
+        assert_eq!(scheduler.len(), 4);
+
+        let first = scheduler.pop_ready().unwrap();
+        let second = scheduler.pop_ready().unwrap();
+        assert_ne!(first.host(), second.host());
+        assert_eq!(scheduler.pop_ready(), None);
+        assert_eq!(
+            scheduler.next_ready_at(),
+            Some(clock.now() + Duration::from_secs(10))
+        );
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(scheduler.pop_ready(), Some(url("https://slow.example/b")));
+        assert_eq!(scheduler.pop_ready(), None);
+
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(scheduler.pop_ready(), Some(url("https://rate.example/b")));
+        assert!(scheduler.is_empty());
+        assert_eq!(scheduler.next_ready_at(), None);
+    }
+
+    #[test]
+    fn forget_idle_hosts() {
+        let (mut scheduler, clock) = scheduler();
+        scheduler.set_default_delay(Duration::from_secs(2));
+        for name in ["slow.example", "other.example"] {
+            scheduler.push(url(&format!("https://{name}/"))).unwrap();
+        }
+        scheduler.record_access(&host("rate.example"));
+        assert!(scheduler.pop_ready().is_some());
+        assert!(scheduler.pop_ready().is_some());
+        assert_eq!(scheduler.tracked_hosts(), 3);
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(scheduler.pop_ready(), None);
+        assert_eq!(scheduler.tracked_hosts(), 2);
+        assert!(!scheduler.is_ready(&host("slow.example")));
+
+        clock.advance(Duration::from_secs(28));
+        assert_eq!(scheduler.pop_ready(), None);
+        assert_eq!(scheduler.tracked_hosts(), 0);
+        assert!(scheduler.is_ready(&host("slow.example")));
+    }
+
+    #[test]
+    fn changed_delays() {
+        let (mut scheduler, clock) = scheduler();
+        for page in ["a", "b"] {
+            scheduler
+                .push(url(&format!("https://new.example/{page}")))
+                .unwrap();
+        }
+        assert_eq!(scheduler.pop_ready(), Some(url("https://new.example/a")));
+        scheduler
+            .meta_mut()
+            .try_add_robots("new.example", "User-agent: *\nCrawl-delay: 5");
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(scheduler.pop_ready(), None);
+        assert_eq!(
+            scheduler.next_ready_at(),
+            Some(clock.now() + Duration::from_secs(4))
+        );
+
+        scheduler.set_default_delay(Duration::from_secs(3));
+        scheduler.meta_mut().remove(&host("new.example"));
+        assert_eq!(
+            scheduler.next_ready_at(),
+            Some(clock.now() + Duration::from_secs(2))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use nicebot::{Permission, SingleBot};
+    use nicebot::{ContentSignals, Permission, RequestRate, SingleBot};
+    use std::time::Duration;
 
     #[test]
     fn from_str() {
@@ -15,6 +16,129 @@ mod tests {
         assert_eq!(bot.check("/abc"), Permission::Allowed);
     }
 
+    #[test]
+    fn canonical() {
+        const INPUT: &str = r#"
+            User-agent: *
+            Disallow: /private/
+            Disallow: /private/drafts/
+            Allow: /public
+            Disallow: /tmp
+            Disallow: /tmp*
+            Sitemap: https://example.com/b.xml
+            Sitemap: https://example.com/a.xml
+        "#;
+
+        const MINIMAL: &str = r#"
+            User-agent: *
+            Sitemap: https://example.com/a.xml
+            Disallow: /tmp*
+            Allow: /public
+            Disallow: /private/
+            Sitemap: https://example.com/b.xml
+        "#;
+
+        let bot = SingleBot::from(INPUT);
+        let minimal = SingleBot::from(MINIMAL);
+
+        assert_ne!(bot, minimal);
+        assert!(bot.equivalent(&minimal));
+        assert_eq!(bot.canonical(), minimal.canonical());
+        assert_eq!(
+            bot.canonical().check("/private/drafts/x"),
+            Permission::Denied
+        );
+        assert_eq!(bot.canonical().check("/tmpfile"), Permission::Denied);
+
+        let changed = SingleBot::from("User-agent: *\nDisallow: /private/\nAllow: /public");
+        assert!(!bot.equivalent(&changed));
+        assert_ne!(bot.canonical(), changed.canonical());
+
+        let large = SingleBot::from(std::fs::read_to_string("test-data/wikipedia.txt").unwrap());
+        let canonical = large.canonical();
+        assert!(canonical.equivalent(&large));
+        assert_eq!(canonical.canonical(), canonical);
+
+        // No rule matches `/ab` literally, yet the added rule changes its decision.
+        let wild = SingleBot::from("User-agent: *\nDisallow: /a\nAllow: /*b");
+        let extended = SingleBot::from("User-agent: *\nDisallow: /a\nAllow: /*b\nDisallow: /a*");
+        assert_ne!(wild.check("/ab"), extended.check("/ab"));
+        assert!(!wild.equivalent(&extended));
+        assert!(wild.canonical().equivalent(&wild));
+        assert!(extended.canonical().equivalent(&extended));
+    }
+
+    #[test]
+    fn crawl_delay() {
+        const INPUT: &str = r#"
+            User-agent: *
+            Crawl-delay: 2.5
+            Request-rate: 3/1m
+
+            User-agent: examplebot
+            Crawl-delay: 7
+        "#;
+
+        let bot = SingleBot::from(INPUT);
+
+        assert_eq!(bot.crawl_delay(), Some(Duration::from_millis(2500)));
+        assert_eq!(
+            bot.request_rate().map(|rate| rate.interval()),
+            Some(Duration::from_secs(20))
+        );
+
+        let mut bot = SingleBot::new(Some("examplebot".into()));
+        bot += INPUT;
+
+        assert_eq!(bot.crawl_delay(), Some(Duration::from_secs(7)));
+        assert_eq!(bot.request_rate(), None);
+        assert_eq!(RequestRate::parse("0/5"), None);
+        assert_eq!(RequestRate::parse("1/5x"), None);
+        assert_eq!(RequestRate::parse("1/999999999999999999d"), None);
+
+        let huge = SingleBot::from("User-agent: *\nCrawl-delay: 1e19");
+        assert_eq!(huge.crawl_delay(), Some(Duration::from_secs(24 * 60 * 60)));
+        assert_eq!(
+            SingleBot::from("User-agent: *\nCrawl-delay: NaN").crawl_delay(),
+            None
+        );
+    }
+
+    #[test]
+    fn content_signals() {
+        const INPUT: &str = r#"
+            # As managed by Cloudflare
+            User-agent: *
+            Content-Signal: search=yes, ai-train=no
+            Content-Signal: ai-input=yes, search=no
+            Allow: /
+
+            User-agent: examplebot
+            Content-signal: AI-Train=Yes
+            Disallow: /private
+        "#;
+
+        let bot = SingleBot::from(INPUT);
+        let signals = bot.content_signals().unwrap();
+        assert_eq!(
+            signals,
+            ContentSignals {
+                search: Some(true),
+                ai_input: Some(true),
+                ai_train: Some(false),
+            }
+        );
+        assert_eq!(signals.to_string(), "search=yes, ai-input=yes, ai-train=no");
+        assert_eq!(bot.check("/private"), Permission::Allowed);
+
+        let mut bot = SingleBot::new(Some("examplebot".into()));
+        bot += INPUT;
+        assert_eq!(bot.content_signals().unwrap().ai_train, Some(true));
+        assert_eq!(bot.content_signals().unwrap().search, None);
+
+        assert_eq!(ContentSignals::parse("search=maybe, other=no"), None);
+    }
+
     #[test]
     fn from_file() {
         let input = std::fs::File::open("test-data/tor.txt").unwrap();
@@ -0,0 +1,183 @@
+#[cfg(all(test, feature = "sitemap"))]
+mod tests {
+    use std::io::{Read, Write};
+
+    use nicebot::{ChangeFreq, SingleBot, SitemapEntry, SitemapItem, SitemapReader};
+
+    const URLSET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <url>
+                <loc>https://example.com/?a=1&amp;b=2</loc>
+                <lastmod>2024-01-31</lastmod>
+                <changefreq>Weekly</changefreq>
+                <priority>0.8</priority>
+            </url>
+            <url>
+                <loc><![CDATA[https://example.com/about]]></loc>
+            </url>
+        </urlset>
+    "#;
+
+    #[test]
+    fn urlset() {
+        let items: Vec<_> = SitemapReader::from(URLSET).map(Result::unwrap).collect();
+
+        assert_eq!(
+            items,
+            vec![
+                SitemapItem::Url(SitemapEntry {
+                    loc: "https://example.com/?a=1&b=2".into(),
+                    lastmod: Some("2024-01-31".into()),
+                    changefreq: Some(ChangeFreq::Weekly),
+                    priority: Some(0.8),
+                }),
+                SitemapItem::Url(SitemapEntry {
+                    loc: "https://example.com/about".into(),
+                    ..Default::default()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn image_sitemap() {
+        const IMAGES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+                xmlns:image="http://www.google.com/schemas/sitemap-image/1.1"
+                xmlns:video="http://www.google.com/schemas/sitemap-video/1.1">
+                <url>
+                    <loc>https://ex.com/page</loc>
+                    <image:image>
+                        <image:loc>https://ex.com/img.jpg</image:loc>
+                    </image:image>
+                    <video:video>
+                        <video:player_loc>https://ex.com/player</video:player_loc>
+                        <video:priority>0.1</video:priority>
+                    </video:video>
+                    <lastmod>2024-01-31</lastmod>
+                </url>
+                <url>
+                    <image:image>
+                        <image:loc>https://ex.com/orphan.jpg</image:loc>
+                    </image:image>
+                </url>
+            </urlset>
+        "#;
+
+        let items: Vec<_> = SitemapReader::from(IMAGES).map(Result::unwrap).collect();
+
+        assert_eq!(
+            items,
+            vec![SitemapItem::Url(SitemapEntry {
+                loc: "https://ex.com/page".into(),
+                lastmod: Some("2024-01-31".into()),
+                ..Default::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn sitemap_index() {
+        const INDEX: &str = r#"
+            <sm:sitemapindex xmlns:sm="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <sm:sitemap>
+                    <sm:loc>https://example.com/sitemap1.xml.gz</sm:loc>
+                    <sm:lastmod>2004-10-01T18:23:17+00:00</sm:lastmod>
+                </sm:sitemap>
+            </sm:sitemapindex>
+        "#;
+
+        let items: Vec<_> = SitemapReader::from(INDEX).map(Result::unwrap).collect();
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], SitemapItem::Sitemap(_)));
+        assert_eq!(items[0].loc(), "https://example.com/sitemap1.xml.gz");
+        assert_eq!(
+            items[0].entry().lastmod.as_deref(),
+            Some("2004-10-01T18:23:17+00:00")
+        );
+    }
+
+    #[test]
+    fn text() {
+        let input = "https://example.com/a\r\n\r\n  https://example.com/b  \n";
+
+        let locs: Vec<_> = SitemapReader::from(input)
+            .map(|item| item.unwrap().loc().to_owned())
+            .collect();
+
+        assert_eq!(locs, vec!["https://example.com/a", "https://example.com/b"]);
+    }
+
+    #[test]
+    fn gzip() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(URLSET.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let reader = SitemapReader::try_from(compressed.as_slice()).unwrap();
+
+        assert_eq!(reader.count(), 2);
+        assert!(SitemapReader::try_from([0x1f, 0x8b, 0, 0, 0].as_slice()).is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        let mut reader = SitemapReader::from(
+            "<urlset><url><loc>https://example.com/</loc></url></sitemapindex>",
+        );
+
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    /// Generates a sitemap with a huge number of URLs without ever holding it in memory.
+    struct Generator {
+        next: usize,
+        total: usize,
+        pending: Vec<u8>,
+    }
+
+    impl Read for Generator {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pending.is_empty() {
+                self.pending = match self.next {
+                    0 => b"<urlset>".to_vec(),
+                    n if n <= self.total => {
+                        format!("<url><loc>https://example.com/{n}</loc></url>").into_bytes()
+                    }
+                    n if n == self.total + 1 => b"</urlset>".to_vec(),
+                    _ => return Ok(0),
+                };
+                self.next += 1;
+            }
+            let len = buf.len().min(self.pending.len());
+            buf[..len].copy_from_slice(&self.pending[..len]);
+            self.pending.drain(..len);
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn streaming() {
+        let generator = Generator {
+            next: 0,
+            total: 200_000,
+            pending: Vec::new(),
+        };
+
+        let reader = SitemapReader::new(std::io::BufReader::new(generator)).unwrap();
+
+        assert_eq!(reader.count(), 200_000);
+    }
+
+    #[test]
+    fn robots_sitemaps() {
+        let robots = std::fs::read_to_string("test-data/gmail.txt").unwrap();
+
+        let bot = SingleBot::from(robots);
+
+        assert_eq!(bot.sitemaps(), ["https://www.google.com/gmail/sitemap.xml"]);
+    }
+}
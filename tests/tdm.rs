@@ -0,0 +1,105 @@
+#[cfg(all(test, feature = "tdm"))]
+mod tests {
+    use nicebot::{CheckURL, Permission, TdmBot, TdmError, TdmPolicy, TdmRep};
+    use url::{Host, Url};
+
+    const TDMREP: &str = r#"[
+        {"location": "/", "tdm-reservation": 1, "tdm-policy": "https://example.com/policy.json"},
+        {"location": "/press/*", "tdm-reservation": 0},
+        {"location": "/blog/*.html$", "tdm-reservation": "1"}
+    ]"#;
+
+    const AI_TXT: &str = r#"
+        # Spawning AI
+        User-Agent: *
+        Disallow: *.jpg
+        Disallow: *.png
+        Allow: /public
+    "#;
+
+    fn host(name: &str) -> Host<String> {
+        Host::parse(name).unwrap()
+    }
+
+    #[test]
+    fn tdmrep() {
+        let tdmrep = TdmRep::parse(TDMREP).unwrap();
+
+        let root = tdmrep.policy("/about").unwrap();
+        assert!(root.reserved);
+        assert_eq!(
+            root.policy.as_deref(),
+            Some("https://example.com/policy.json")
+        );
+        assert!(!tdmrep.policy("/press/release").unwrap().reserved);
+        assert!(tdmrep.policy("/blog/post.html").unwrap().reserved);
+        assert_eq!(TdmRep::parse("[]").unwrap().policy("/"), None);
+
+        assert!(matches!(TdmRep::parse("{"), Err(TdmError::Json(_))));
+        assert!(matches!(
+            TdmRep::parse(r#"[{"location": "/"}]"#),
+            Err(TdmError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn headers_and_meta() {
+        assert_eq!(
+            TdmPolicy::from_headers(Some("1"), Some("https://example.com/policy.json")),
+            Some(TdmPolicy {
+                reserved: true,
+                policy: Some("https://example.com/policy.json".to_owned()),
+            })
+        );
+        assert_eq!(TdmPolicy::from_headers(Some("yes"), None), None);
+        assert_eq!(TdmPolicy::from_headers(None, None), None);
+
+        let html = r#"<head><meta name="tdm-reservation" content="0"></head>"#;
+        assert_eq!(
+            TdmPolicy::from_html(html),
+            Some(TdmPolicy {
+                reserved: false,
+                policy: None,
+            })
+        );
+    }
+
+    #[test]
+    fn check() {
+        let mut bot = TdmBot::new(Some("gptbot".to_owned()));
+        bot.add_tdmrep(host("a.example"), TDMREP).unwrap();
+        bot.add_ai_txt(host("b.example"), AI_TXT);
+        assert!(bot.add_tdmrep(host("a.example"), "[1]").is_err());
+
+        assert_eq!(bot.check("https://a.example/about"), Ok(Permission::Denied));
+        assert_eq!(
+            bot.check("https://a.example/press/release"),
+            Ok(Permission::Allowed)
+        );
+        assert_eq!(
+            bot.check("https://b.example/img/cat.jpg"),
+            Ok(Permission::Denied)
+        );
+        assert_eq!(
+            bot.check("https://b.example/public/page"),
+            Ok(Permission::Allowed)
+        );
+        assert_eq!(
+            bot.check("https://b.example/page"),
+            Ok(Permission::Unspecified)
+        );
+        assert_eq!(bot.check("https://c.example/"), Ok(Permission::Unspecified));
+
+        let url = Url::parse("https://a.example/").unwrap();
+        assert!(bot.policy(&url).is_some_and(|policy| policy.reserved));
+
+        // Either reservation denies mining.
+        bot.add_ai_txt(host("a.example"), AI_TXT);
+        assert_eq!(
+            bot.check("https://a.example/press/photo.png"),
+            Ok(Permission::Denied)
+        );
+        assert!(bot.remove(&host("a.example")));
+        assert_eq!(bot.check(&url), Ok(Permission::Unspecified));
+    }
+}
@@ -116,4 +116,84 @@ mod tests {
         assert!(trie.has("/abcx"));
         assert!(trie.has("/x/abc/x"));
     }
+
+    #[test]
+    fn trie_get_match() {
+        let mut trie = PrefixTrie::new();
+
+        trie.insert("", 0);
+        trie.insert("/a", 1);
+        trie.insert("/a*.pdf\0", 2);
+
+        assert_eq!(trie.get_match("/abc"), Some((1, "/a".to_owned())));
+        assert_eq!(
+            trie.get_match("/abc.pdf"),
+            Some((2, "/a*.pdf\0".to_owned()))
+        );
+        assert_eq!(trie.get_match("/b"), Some((0, String::new())));
+    }
+
+    #[test]
+    fn trie_get_by() {
+        let mut trie = PrefixTrie::new();
+
+        trie.insert("", 0);
+        trie.insert("/a", 1);
+        trie.insert("/a/b", 2);
+
+        let odd = |value: i32| (value % 2 == 1).then_some(value * 10);
+        assert_eq!(trie.get_by("/a/b", odd), Some(10));
+        assert_eq!(trie.get_by("/b", odd), None);
+        assert_eq!(
+            trie.get_match_by("/a/b/c", |value| (value != 2).then_some(value)),
+            Some((1, "/a".to_owned()))
+        );
+    }
+
+    #[test]
+    fn trie_entries() {
+        let mut trie = PrefixTrie::new();
+
+        trie.insert("/a", 1);
+        trie.insert("/a*b", 2);
+        trie.insert("/c\0", 3);
+
+        let mut entries = trie.entries();
+        entries.sort();
+
+        assert_eq!(
+            entries,
+            [
+                ("/a".to_owned(), 1),
+                ("/a*b".to_owned(), 2),
+                ("/c\0".to_owned(), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn trie_equivalent() {
+        let mut trie = PrefixTrie::new();
+        trie.insert("/a", 1);
+        trie.insert("/a/b", 1);
+        trie.insert("/c*", 2);
+
+        let mut minimal = PrefixTrie::new();
+        minimal.insert("/a", 1);
+        minimal.insert("/c", 2);
+
+        assert!(trie.equivalent(&minimal));
+
+        minimal.insert("/a/b\0", 2);
+        assert!(!trie.equivalent(&minimal));
+
+        // `/x*y` decides `/xzy`, which no key spells out.
+        let mut wild = PrefixTrie::new();
+        wild.insert("/x", 1);
+        let mut extended = wild.clone();
+        extended.insert("/x*y", 1);
+        assert!(wild.equivalent(&extended));
+        extended.insert("/x*y", 2);
+        assert!(!wild.equivalent(&extended));
+    }
 }
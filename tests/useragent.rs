@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use nicebot::{AgentBot, Permission, SingleBot, UserAgent};
+
+    const ROBOTS: &str = r"
+        User-agent: *
+        Disallow: /private
+
+        User-agent: Googlebot
+        Disallow: /search
+
+        User-agent: ExampleBot
+        Disallow: /
+    ";
+
+    #[test]
+    fn product_tokens() {
+        let tokens = |header: &str| UserAgent::parse(header).tokens().to_vec();
+
+        assert_eq!(
+            tokens("Mozilla/5.0 (compatible; ExampleBot/2.1; +https://example.com/bot)"),
+            vec!["ExampleBot"]
+        );
+        assert_eq!(
+            tokens(
+                "Mozilla/5.0 (Linux; Android 6.0.1; Nexus 5X Build/MMB29P) AppleWebKit/537.36 \
+                 (KHTML, like Gecko) Chrome/125.0.6422.175 Mobile Safari/537.36 \
+                 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+            ),
+            vec!["Googlebot"]
+        );
+        assert_eq!(
+            tokens(
+                "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; GPTBot/1.2; +https://openai.com/gptbot)"
+            ),
+            vec!["GPTBot"]
+        );
+        assert_eq!(tokens("Googlebot-Image/1.0"), vec!["Googlebot-Image"]);
+        assert_eq!(tokens("curl/8.5.0 (bot@example.com)"), vec!["curl"]);
+        assert_eq!(tokens("nicebot"), vec!["nicebot"]);
+        assert!(tokens("Mozilla/5.0 (X11; Linux x86_64)").is_empty());
+    }
+
+    #[test]
+    fn fallbacks() {
+        let user_agent = UserAgent::parse("Googlebot-Image/1.0");
+        assert_eq!(user_agent.product_token(), Some("Googlebot-Image"));
+        assert_eq!(
+            user_agent.candidates(),
+            vec!["googlebot-image", "googlebot"]
+        );
+
+        let bot = AgentBot::from(ROBOTS);
+        assert_eq!(bot.resolve(&user_agent).as_deref(), Some("googlebot"));
+        let view = bot.for_user_agent(&user_agent);
+        assert_eq!(view.check("/search"), Permission::Denied);
+        assert_eq!(view.check("/private"), Permission::Unspecified);
+
+        let other = UserAgent::parse("Mozilla/5.0 (compatible; OtherBot/1.0)");
+        assert_eq!(bot.resolve(&other), None);
+        assert_eq!(
+            bot.for_user_agent(&other).check("/private"),
+            Permission::Denied
+        );
+    }
+
+    #[test]
+    fn single_bot() {
+        let user_agent =
+            UserAgent::parse("Mozilla/5.0 (compatible; ExampleBot/2.1; +https://example.com/bot)");
+        let bot = SingleBot::from_user_agent(&user_agent, ROBOTS);
+        assert_eq!(bot.check("/public"), Permission::Denied);
+
+        let bot = SingleBot::from_user_agent(&UserAgent::parse("Googlebot-Image/1.0"), ROBOTS);
+        assert_eq!(bot.check("/search"), Permission::Denied);
+        assert_eq!(bot.check("/private"), Permission::Unspecified);
+
+        let bot = SingleBot::from_user_agent(&UserAgent::parse("OtherBot/1.0"), ROBOTS);
+        assert_eq!(bot.check("/private"), Permission::Denied);
+        assert_eq!(bot.check("/search"), Permission::Unspecified);
+    }
+}